@@ -1,13 +1,36 @@
-use crate::errors::RequestError;
+use reqwest::Method;
+use serde::de::IgnoredAny;
 use crate::LuckClient;
+use crate::endpoint::{json_body, Endpoint};
+use crate::errors::RequestError;
 use crate::models::Action;
 
+/// `POST /action`, submit an action to the action log.
+#[derive(Debug, Clone)]
+pub struct SubmitAction {
+    pub action: Action,
+}
+
+impl Endpoint for SubmitAction {
+    type Response = IgnoredAny;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["action".to_string()]
+    }
+
+    fn body(&self) -> Result<Option<Vec<u8>>, RequestError> {
+        json_body(&self.action)
+    }
+}
+
 impl LuckClient {
     /// Submit an action to the server.
     pub async fn submit_action(&self, action: Action) -> Result<(), RequestError> {
-        let url = self.base_url.join("/action")?;
-        let response = self.client.post(url).json(&action).send().await?;
-        response.error_for_status()?;
+        self.execute(SubmitAction { action }).await?;
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,139 @@
+//! A synchronous client, for tools that don't use async Rust.
+//!
+//! [`LuckClient`] wraps the async [`crate::LuckClient`] and drives it on a private
+//! single-threaded runtime, so callers don't need to set up a runtime of their own.
+//! Its methods must not be called from within an async runtime.
+//!
+//! ```rust
+//! use luckperms_rs::blocking::LuckClient;
+//!
+//! fn main() {
+//!     let client = LuckClient::try_new("http://localhost:8080".to_string(), "YOUR API KEY".to_string()).unwrap();
+//! }
+//! ```
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use serde::Serialize;
+use tokio::runtime::Runtime;
+use uuid::Uuid;
+use crate::endpoint::Endpoint;
+use crate::errors::{ClientCreationError, RequestError};
+use crate::models::{Action, Group, GroupSearchResult, Health, Metadata, Node, PermissionCheckResult, TemporaryMergeStrategy, Track, TrackMoveResponse, User, UserIdentifier, UserSearchResult};
+use crate::requests::{PermissionCheckRequest, SearchRequest};
+
+/// A blocking client for interacting with a LuckPerms instance.
+///
+/// Cloning is cheap, clones share the async client and its runtime.
+#[derive(Clone)]
+pub struct LuckClient {
+    client: crate::LuckClient,
+    runtime: Arc<Runtime>,
+}
+
+impl LuckClient {
+    /// Create a new blocking LuckClient.
+    pub fn try_new(base_url: String, api_key: String) -> Result<Self, ClientCreationError> {
+        Self::from_async(crate::LuckClient::try_new(base_url, api_key)?)
+    }
+
+    /// Wrap an async client, e.g. one configured with [`crate::LuckClient::builder`].
+    pub fn from_async(client: crate::LuckClient) -> Result<Self, ClientCreationError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(ClientCreationError::Runtime)?;
+
+        Ok(Self {
+            client,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// The async client this client wraps.
+    pub fn as_async(&self) -> &crate::LuckClient {
+        &self.client
+    }
+
+    /// Run a call on the async client to completion, for methods without a blocking version.
+    pub fn block_on<'a, F: Future>(&'a self, call: impl FnOnce(&'a crate::LuckClient) -> F) -> F::Output {
+        self.runtime.block_on(call(&self.client))
+    }
+
+    /// Call an endpoint, see [`crate::LuckClient::execute`].
+    pub fn execute<E: Endpoint>(&self, endpoint: E) -> Result<E::Response, RequestError> {
+        self.block_on(|client| client.execute(endpoint))
+    }
+
+    /// Send a custom message on a channel, encoding the payload as JSON.
+    ///
+    /// The payload is always JSON-encoded, so a string is sent quoted: `&"hello"` arrives as `"\"hello\""`.
+    /// Receivers can decode it again with [`crate::models::CustomMessage::payload_json`].
+    pub fn send_custom_message<T: Serialize>(&self, channel_id: String, payload: &T) -> Result<(), RequestError> {
+        self.block_on(|client| client.send_custom_message(channel_id, payload))
+    }
+}
+
+/// Generate blocking versions of async [`crate::LuckClient`] methods.
+macro_rules! blocking_methods {
+    ($(fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)*) => {
+        impl LuckClient {
+            $(
+                #[doc = concat!("Blocking version of [`crate::LuckClient::", stringify!($name), "`].")]
+                pub fn $name(&self $(, $arg: $ty)*) -> $ret {
+                    self.block_on(|client| client.$name($($arg),*))
+                }
+            )*
+        }
+    };
+}
+
+blocking_methods! {
+    fn users(&self) -> Result<Vec<Uuid>, RequestError>;
+    fn create_user(&self, user: UserIdentifier) -> Result<User, RequestError>;
+    fn username_lookup(&self, username: String) -> Result<UserIdentifier, RequestError>;
+    fn uuid_lookup(&self, uuid: Uuid) -> Result<UserIdentifier, RequestError>;
+    fn user_search(&self, search: SearchRequest) -> Result<Vec<UserSearchResult>, RequestError>;
+    fn get_user(&self, uuid: Uuid) -> Result<Option<User>, RequestError>;
+    fn update_username(&self, uuid: Uuid, username: String) -> Result<(), RequestError>;
+    fn delete_user(&self, uuid: Uuid) -> Result<(), RequestError>;
+    fn get_user_nodes(&self, uuid: Uuid) -> Result<Vec<Node>, RequestError>;
+    fn add_user_node(&self, uuid: Uuid, node: Node, merge_strategy: TemporaryMergeStrategy) -> Result<Vec<Node>, RequestError>;
+    fn add_user_nodes(&self, uuid: Uuid, nodes: Vec<Node>, merge_strategy: TemporaryMergeStrategy) -> Result<Vec<Node>, RequestError>;
+    fn set_user_nodes(&self, uuid: Uuid, nodes: Vec<Node>) -> Result<(), RequestError>;
+    fn delete_user_nodes(&self, uuid: Uuid, nodes: Vec<Node>) -> Result<(), RequestError>;
+    fn get_user_metadata(&self, uuid: Uuid) -> Result<Metadata, RequestError>;
+    fn check_user_permission(&self, uuid: Uuid, permission: String) -> Result<PermissionCheckResult, RequestError>;
+    fn check_user_permission_query(&self, uuid: Uuid, request: PermissionCheckRequest) -> Result<PermissionCheckResult, RequestError>;
+    fn promote_user(&self, uuid: Uuid, track: String) -> Result<TrackMoveResponse, RequestError>;
+    fn demote_user(&self, uuid: Uuid, track: String) -> Result<TrackMoveResponse, RequestError>;
+
+    fn get_groups(&self) -> Result<Vec<String>, RequestError>;
+    fn create_group(&self, name: String) -> Result<Group, RequestError>;
+    fn search_group(&self, search_request: SearchRequest) -> Result<Vec<GroupSearchResult>, RequestError>;
+    fn get_group(&self, name: String) -> Result<Option<Group>, RequestError>;
+    fn delete_group(&self, name: String) -> Result<(), RequestError>;
+    fn get_group_nodes(&self, name: String) -> Result<Vec<Node>, RequestError>;
+    fn add_group_node(&self, name: String, node: Node, merge_strategy: TemporaryMergeStrategy) -> Result<Vec<Node>, RequestError>;
+    fn add_group_nodes(&self, name: String, nodes: Vec<Node>, merge_strategy: TemporaryMergeStrategy) -> Result<Vec<Node>, RequestError>;
+    fn set_group_nodes(&self, name: String, nodes: Vec<Node>) -> Result<(), RequestError>;
+    fn delete_group_node(&self, name: String, nodes: Vec<Node>) -> Result<(), RequestError>;
+    fn get_group_metadata(&self, name: String) -> Result<Metadata, RequestError>;
+    fn check_group_permission(&self, name: String, permission: String) -> Result<PermissionCheckResult, RequestError>;
+    fn check_group_permission_query(&self, name: String, request: PermissionCheckRequest) -> Result<PermissionCheckResult, RequestError>;
+
+    fn submit_action(&self, action: Action) -> Result<(), RequestError>;
+
+    fn get_tracks(&self) -> Result<Vec<String>, RequestError>;
+    fn create_track(&self, name: String) -> Result<Track, RequestError>;
+    fn get_track(&self, name: String) -> Result<Option<Track>, RequestError>;
+    fn update_track(&self, name: String, groups: Vec<String>) -> Result<(), RequestError>;
+    fn delete_track(&self, name: String) -> Result<(), RequestError>;
+
+    fn push_network_update(&self) -> Result<(), RequestError>;
+    fn push_user_update(&self, uuid: Uuid) -> Result<(), RequestError>;
+
+    fn health(&self) -> Result<Health, RequestError>;
+    fn wait_until_healthy(&self, timeout: Duration) -> Result<Health, RequestError>;
+}
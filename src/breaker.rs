@@ -0,0 +1,166 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use crate::errors::RequestError;
+use crate::retry::is_transient;
+
+/// The state of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CircuitState {
+    /// Requests are sent as usual.
+    Closed,
+    /// Requests fail immediately with [`RequestError::CircuitOpen`].
+    Open,
+    /// The instance is being probed through `/health` to decide whether to close again.
+    HalfOpen,
+}
+
+type StateCallback = Box<dyn Fn(CircuitState, CircuitState) + Send + Sync>;
+
+/// Fails requests fast while the LuckPerms instance appears to be down.
+///
+/// The breaker opens after a number of consecutive transient failures. Once the open
+/// duration has passed, the next request probes `/health`, closing the breaker again if
+/// the instance reports itself healthy.
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    on_state_change: Option<StateCallback>,
+    inner: Mutex<Inner>,
+}
+
+struct Inner {
+    state: CircuitState,
+    failures: u32,
+    opened_at: Option<Instant>,
+}
+
+/// What a request should do after asking the breaker for permission.
+pub(crate) enum Permit<'a> {
+    Proceed,
+    Probe(Probe<'a>),
+}
+
+/// A `/health` probe in progress. If it is dropped before finishing, e.g. because the
+/// request was cancelled, the breaker opens again so that a later request can probe.
+pub(crate) struct Probe<'a> {
+    breaker: &'a CircuitBreaker,
+    finished: bool,
+}
+
+impl Probe<'_> {
+    pub(crate) fn finish(mut self, healthy: bool) {
+        self.finished = true;
+        self.breaker.probe_finished(healthy);
+    }
+}
+
+impl Drop for Probe<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.breaker.probe_finished(false);
+        }
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            open_duration,
+            on_state_change: None,
+            inner: Mutex::new(Inner {
+                state: CircuitState::Closed,
+                failures: 0,
+                opened_at: None,
+            }),
+        }
+    }
+
+    /// Call `callback` with the previous and new state whenever the state changes.
+    pub fn on_state_change(mut self, callback: impl Fn(CircuitState, CircuitState) + Send + Sync + 'static) -> Self {
+        self.on_state_change = Some(Box::new(callback));
+        self
+    }
+
+    pub fn state(&self) -> CircuitState {
+        self.inner.lock().unwrap().state
+    }
+
+    pub(crate) fn acquire(&self) -> Result<Permit<'_>, RequestError> {
+        let mut inner = self.inner.lock().unwrap();
+        match inner.state {
+            CircuitState::Closed => Ok(Permit::Proceed),
+            CircuitState::HalfOpen => Err(RequestError::CircuitOpen),
+            CircuitState::Open => {
+                if inner.opened_at.is_some_and(|at| at.elapsed() < self.open_duration) {
+                    return Err(RequestError::CircuitOpen);
+                }
+                let change = transition(&mut inner, CircuitState::HalfOpen);
+                drop(inner);
+                self.notify(change);
+                Ok(Permit::Probe(Probe {
+                    breaker: self,
+                    finished: false,
+                }))
+            },
+        }
+    }
+
+    fn probe_finished(&self, healthy: bool) {
+        let mut inner = self.inner.lock().unwrap();
+        let change = if healthy {
+            inner.failures = 0;
+            transition(&mut inner, CircuitState::Closed)
+        } else {
+            inner.opened_at = Some(Instant::now());
+            transition(&mut inner, CircuitState::Open)
+        };
+        drop(inner);
+        self.notify(change);
+    }
+
+    pub(crate) fn record<T>(&self, result: &Result<T, RequestError>) {
+        let mut inner = self.inner.lock().unwrap();
+        let change = match result {
+            Err(error) if is_transient(error) => {
+                inner.failures += 1;
+                if inner.state == CircuitState::Closed && inner.failures >= self.failure_threshold {
+                    inner.opened_at = Some(Instant::now());
+                    transition(&mut inner, CircuitState::Open)
+                } else {
+                    None
+                }
+            },
+            _ => {
+                inner.failures = 0;
+                None
+            },
+        };
+        drop(inner);
+        self.notify(change);
+    }
+
+    /// Call the state change callback, outside the lock so it may use the breaker itself.
+    fn notify(&self, change: Option<(CircuitState, CircuitState)>) {
+        if let (Some(callback), Some((previous, state))) = (&self.on_state_change, change) {
+            callback(previous, state);
+        }
+    }
+}
+
+/// Change the state, returning the previous and new state if it changed.
+fn transition(inner: &mut Inner, state: CircuitState) -> Option<(CircuitState, CircuitState)> {
+    let previous = std::mem::replace(&mut inner.state, state);
+    (previous != state).then_some((previous, state))
+}
+
+impl Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("failure_threshold", &self.failure_threshold)
+            .field("open_duration", &self.open_duration)
+            .field("state", &self.state())
+            .finish()
+    }
+}
\ No newline at end of file
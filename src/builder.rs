@@ -0,0 +1,255 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use reqwest::header::HeaderValue;
+use reqwest::{Certificate, Client, Proxy, Url};
+use crate::errors::ClientCreationError;
+use crate::{ClientInner, LuckClient};
+use crate::breaker::CircuitBreaker;
+use crate::cassette::{RecordingTransport, ReplayTransport};
+use crate::limiter::Limiter;
+use crate::metrics::MetricsRecorder;
+use crate::models::ContextSet;
+use crate::middleware::{Middleware, MiddlewareTransport};
+use crate::retry::{NoRetry, RetryPolicy};
+use crate::transport::{ReqwestTransport, Transport};
+
+/// A builder for a [`LuckClient`] with custom HTTP settings.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use luckperms_rs::LuckClient;
+///
+/// fn main() {
+///     let client = LuckClient::builder("http://localhost:8080".to_string(), "YOUR API KEY".to_string())
+///         .connect_timeout(Duration::from_secs(5))
+///         .timeout(Duration::from_secs(30))
+///         .build()
+///         .unwrap();
+/// }
+/// ```
+#[derive(Debug)]
+pub struct LuckClientBuilder {
+    base_url: String,
+    api_key: String,
+    client: Option<Client>,
+    transport: Option<Arc<dyn Transport>>,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    proxies: Vec<Proxy>,
+    root_certificates: Vec<Certificate>,
+    accept_invalid_certs: bool,
+    retry_policy: Arc<dyn RetryPolicy>,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    record_cassette: Option<PathBuf>,
+    replay_cassette: Option<PathBuf>,
+    metrics_recorder: Option<Arc<dyn MetricsRecorder>>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    limiter: Option<Arc<Limiter>>,
+    default_contexts: ContextSet,
+    #[cfg(feature = "tracing")]
+    log_payloads: bool,
+}
+
+impl LuckClientBuilder {
+    pub fn new(base_url: String, api_key: String) -> Self {
+        Self {
+            base_url,
+            api_key,
+            client: None,
+            transport: None,
+            connect_timeout: None,
+            timeout: None,
+            user_agent: None,
+            proxies: Vec::new(),
+            root_certificates: Vec::new(),
+            accept_invalid_certs: false,
+            retry_policy: Arc::new(NoRetry),
+            circuit_breaker: None,
+            record_cassette: None,
+            replay_cassette: None,
+            metrics_recorder: None,
+            middleware: Vec::new(),
+            limiter: None,
+            default_contexts: ContextSet::new(),
+            #[cfg(feature = "tracing")]
+            log_payloads: false,
+        }
+    }
+
+    /// Use an existing `reqwest::Client`, sharing its connection pool.
+    ///
+    /// The connect timeout, user agent, proxy and TLS settings of this builder are ignored
+    /// when a client is provided, configure them on the client instead. The request
+    /// timeout still applies.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Send requests through a custom [`Transport`] instead of reqwest.
+    ///
+    /// All HTTP settings of this builder, including [`LuckClientBuilder::client`], are ignored
+    /// when a transport is provided.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Timeout for establishing a connection.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for an entire request, from connecting until the response body is read.
+    ///
+    /// Event streams are exempt, since their body never ends.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Trust an additional root certificate, e.g. a private CA.
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Accept invalid TLS certificates. Only use this for local testing.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    /// Retry failed requests according to a policy, see [`crate::retry::ExponentialBackoff`].
+    pub fn retry_policy(mut self, policy: impl RetryPolicy + 'static) -> Self {
+        self.retry_policy = Arc::new(policy);
+        self
+    }
+
+    /// Fail requests fast while the LuckPerms instance is down.
+    pub fn circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(Arc::new(breaker));
+        self
+    }
+
+    /// Record every request and response to a cassette file, see [`crate::cassette`].
+    pub fn record_cassette(mut self, path: impl Into<PathBuf>) -> Self {
+        self.record_cassette = Some(path.into());
+        self
+    }
+
+    /// Serve responses from a previously recorded cassette file instead of sending requests.
+    ///
+    /// This takes precedence over any other transport settings.
+    pub fn replay_cassette(mut self, path: impl Into<PathBuf>) -> Self {
+        self.replay_cassette = Some(path.into());
+        self
+    }
+
+    /// Limit the number of in-flight requests and the request rate, see [`Limiter`].
+    pub fn limiter(mut self, limiter: Limiter) -> Self {
+        self.limiter = Some(Arc::new(limiter));
+        self
+    }
+
+    /// Contexts that permission checks use unless a query sets its own.
+    pub fn default_contexts(mut self, contexts: ContextSet) -> Self {
+        self.default_contexts = contexts;
+        self
+    }
+
+    /// Add a [`Middleware`] that runs around every request, after any added before it.
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Report the endpoint, status, latency and size of every call, see [`crate::metrics`].
+    pub fn metrics_recorder(mut self, recorder: impl MetricsRecorder + 'static) -> Self {
+        self.metrics_recorder = Some(Arc::new(recorder));
+        self
+    }
+
+    /// Include request and response bodies in `trace` level events.
+    ///
+    /// Bodies are redacted by default since they contain node payloads.
+    #[cfg(feature = "tracing")]
+    pub fn log_payloads(mut self, log: bool) -> Self {
+        self.log_payloads = log;
+        self
+    }
+
+    pub fn build(self) -> Result<LuckClient, ClientCreationError> {
+        let base_url = Url::parse(&self.base_url)?;
+        let api_key = HeaderValue::from_str(&self.api_key)?;
+
+        let transport: Arc<dyn Transport> = match (self.replay_cassette, self.transport, self.client) {
+            (Some(path), _, _) => Arc::new(ReplayTransport::load(path)?.base_url(&base_url)),
+            (None, Some(transport), _) => transport,
+            (None, None, Some(client)) => Arc::new(reqwest_transport(client, self.timeout)),
+            (None, None, None) => {
+                let mut builder = reqwest::ClientBuilder::new()
+                    .danger_accept_invalid_certs(self.accept_invalid_certs);
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                for proxy in self.proxies {
+                    builder = builder.proxy(proxy);
+                }
+                for certificate in self.root_certificates {
+                    builder = builder.add_root_certificate(certificate);
+                }
+                Arc::new(reqwest_transport(builder.build()?, self.timeout))
+            },
+        };
+
+        let transport = match self.record_cassette {
+            Some(path) => Arc::new(RecordingTransport::new(transport, path).base_url(&base_url)),
+            None => transport,
+        };
+
+        let transport = if self.middleware.is_empty() {
+            transport
+        } else {
+            Arc::new(MiddlewareTransport::new(transport, self.middleware))
+        };
+
+        Ok(LuckClient {
+            inner: Arc::new(ClientInner {
+                base_url,
+                api_key,
+                default_contexts: self.default_contexts,
+                transport,
+                retry_policy: self.retry_policy,
+                circuit_breaker: self.circuit_breaker,
+                metrics_recorder: self.metrics_recorder,
+                limiter: self.limiter,
+                #[cfg(feature = "tracing")]
+                log_payloads: self.log_payloads,
+            }),
+        })
+    }
+}
+fn reqwest_transport(client: Client, timeout: Option<Duration>) -> ReqwestTransport {
+    let transport = ReqwestTransport::new(client);
+    match timeout {
+        Some(timeout) => transport.timeout(timeout),
+        None => transport,
+    }
+}
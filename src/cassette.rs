@@ -0,0 +1,253 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use futures_util::future::BoxFuture;
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use reqwest::{StatusCode, Url};
+use crate::errors::RequestError;
+use crate::transport::{HttpRequest, HttpResponse, StreamingResponse, Transport};
+
+/// A recording of HTTP exchanges with a LuckPerms instance, stored as JSON.
+///
+/// Requests are recorded by their path relative to the client's base URL, so a cassette
+/// recorded against one instance can be replayed with a client pointed at any other base URL.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedRequest {
+    pub method: String,
+    /// The path and query of the request URL, relative to the base URL.
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+impl Cassette {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CassetteError> {
+        let contents = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CassetteError> {
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+impl RecordedRequest {
+    /// Record a request, removing `base_path` (the path of the base URL) from its path.
+    fn from_request(request: &HttpRequest, base_path: &str) -> Self {
+        let path = request.url.path();
+        let mut path = path.strip_prefix(base_path)
+            .filter(|relative| relative.starts_with('/'))
+            .unwrap_or(path)
+            .to_string();
+        if let Some(query) = request.url.query() {
+            path.push('?');
+            path.push_str(query);
+        }
+
+        Self {
+            method: request.method.to_string(),
+            path,
+            body: request.body.as_deref().map(|body| String::from_utf8_lossy(body).into_owned()),
+        }
+    }
+
+    /// Compare with another request, treating JSON bodies that only differ in formatting as equal.
+    fn matches(&self, other: &RecordedRequest) -> bool {
+        self.method == other.method && self.path == other.path && match (&self.body, &other.body) {
+            (Some(a), Some(b)) => a == b || json_eq(a, b),
+            (a, b) => a == b,
+        }
+    }
+}
+
+impl Display for RecordedRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.method, self.path)?;
+        if let Some(body) = &self.body {
+            write!(f, " with body {}", body)?;
+        }
+        Ok(())
+    }
+}
+
+impl RecordedResponse {
+    fn from_response(response: &HttpResponse) -> Self {
+        Self {
+            status: response.status.as_u16(),
+            body: (!response.body.is_empty()).then(|| response.text()),
+        }
+    }
+
+    fn to_response(&self) -> Result<HttpResponse, RequestError> {
+        let status = StatusCode::from_u16(self.status)
+            .map_err(|e| RequestError::Transport(e.into()))?;
+        let mut headers = HeaderMap::new();
+        if self.body.as_deref().is_some_and(|body| serde_json::from_str::<serde_json::Value>(body).is_ok()) {
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        }
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body: self.body.clone().unwrap_or_default().into_bytes(),
+        })
+    }
+}
+
+/// The path of a base URL without a trailing slash, e.g. `/luckperms` or an empty string.
+fn base_path(base_url: &Url) -> String {
+    base_url.path().trim_end_matches('/').to_string()
+}
+
+fn json_eq(a: &str, b: &str) -> bool {
+    match (serde_json::from_str::<serde_json::Value>(a), serde_json::from_str::<serde_json::Value>(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CassetteError {
+    #[error("Cassette IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Cassette JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("No recorded interaction matches {0}")]
+    Unmatched(RecordedRequest),
+}
+
+/// A [`Transport`] that passes requests on to another transport and records every
+/// exchange to a cassette file.
+///
+/// The file is rewritten after every exchange, so it is complete even if the
+/// process exits without dropping the client. Event streams are passed on unrecorded.
+#[derive(Debug)]
+pub struct RecordingTransport {
+    inner: Arc<dyn Transport>,
+    path: PathBuf,
+    base_path: String,
+    cassette: Mutex<Cassette>,
+}
+
+impl RecordingTransport {
+    pub fn new(inner: Arc<dyn Transport>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            path: path.into(),
+            base_path: String::new(),
+            cassette: Mutex::new(Cassette::default()),
+        }
+    }
+
+    /// Record paths relative to this base URL, so that its path isn't part of the cassette.
+    pub fn base_url(mut self, base_url: &Url) -> Self {
+        self.base_path = base_path(base_url);
+        self
+    }
+
+    fn record(&self, request: RecordedRequest, response: &HttpResponse) -> Result<(), CassetteError> {
+        let mut cassette = self.cassette.lock().unwrap();
+        cassette.interactions.push(Interaction {
+            request,
+            response: RecordedResponse::from_response(response),
+        });
+        cassette.save(&self.path)
+    }
+}
+
+impl Transport for RecordingTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, RequestError>> {
+        Box::pin(async move {
+            let recorded = RecordedRequest::from_request(&request, &self.base_path);
+            let response = self.inner.send(request).await?;
+            self.record(recorded, &response).map_err(|e| RequestError::Transport(e.into()))?;
+            Ok(response)
+        })
+    }
+
+    fn stream(&self, request: HttpRequest) -> BoxFuture<'_, Result<StreamingResponse, RequestError>> {
+        self.inner.stream(request)
+    }
+}
+
+/// A [`Transport`] that serves responses from a cassette instead of sending requests.
+///
+/// Each recorded interaction is used once, in order. A request without a matching
+/// unused interaction fails with [`CassetteError::Unmatched`].
+#[derive(Debug)]
+pub struct ReplayTransport {
+    base_path: String,
+    interactions: Mutex<Vec<(Interaction, bool)>>,
+}
+
+impl ReplayTransport {
+    pub fn new(cassette: Cassette) -> Self {
+        Self {
+            base_path: String::new(),
+            interactions: Mutex::new(cassette.interactions.into_iter().map(|interaction| (interaction, false)).collect()),
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CassetteError> {
+        Ok(Self::new(Cassette::load(path)?))
+    }
+
+    /// Match requests by their path relative to this base URL.
+    pub fn base_url(mut self, base_url: &Url) -> Self {
+        self.base_path = base_path(base_url);
+        self
+    }
+
+    /// The recorded requests that were never replayed.
+    pub fn unused(&self) -> Vec<RecordedRequest> {
+        self.interactions.lock().unwrap().iter()
+            .filter(|(_, used)| !used)
+            .map(|(interaction, _)| interaction.request.clone())
+            .collect()
+    }
+
+    fn replay(&self, request: &HttpRequest) -> Result<HttpResponse, RequestError> {
+        let recorded = RecordedRequest::from_request(request, &self.base_path);
+        let mut interactions = self.interactions.lock().unwrap();
+        let Some((interaction, used)) = interactions.iter_mut()
+            .find(|(interaction, used)| !*used && interaction.request.matches(&recorded)) else {
+            return Err(RequestError::Transport(CassetteError::Unmatched(recorded).into()));
+        };
+
+        *used = true;
+        interaction.response.to_response()
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, RequestError>> {
+        Box::pin(async move { self.replay(&request) })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,95 @@
+use reqwest::header::{HeaderValue, CONTENT_TYPE};
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use crate::errors::RequestError;
+use crate::LuckClient;
+
+/// A typed description of a LuckPerms REST endpoint.
+///
+/// Every method of [`LuckClient`] is backed by an endpoint, and endpoints the crate has
+/// no method for can be called by implementing this trait and using [`LuckClient::execute`].
+///
+/// ```rust
+/// use luckperms_rs::endpoint::Endpoint;
+/// use reqwest::Method;
+///
+/// struct GroupNames;
+///
+/// impl Endpoint for GroupNames {
+///     type Response = Vec<String>;
+///
+///     fn method(&self) -> Method {
+///         Method::GET
+///     }
+///
+///     fn path(&self) -> Vec<String> {
+///         vec!["group".to_string()]
+///     }
+/// }
+/// ```
+pub trait Endpoint {
+    /// The type the response body is parsed as. An empty body is parsed as JSON `null`,
+    /// use `()` or [`serde::de::IgnoredAny`] for endpoints without a useful response.
+    type Response: DeserializeOwned;
+
+    fn method(&self) -> Method;
+
+    /// The path segments, appended to the base URL and percent-encoded.
+    fn path(&self) -> Vec<String>;
+
+    /// The query parameters.
+    fn query(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// The JSON request body, see [`json_body`].
+    fn body(&self) -> Result<Option<Vec<u8>>, RequestError> {
+        Ok(None)
+    }
+
+    /// Whether sending the request more than once has the same effect as sending it once.
+    fn idempotent(&self) -> bool {
+        matches!(self.method(), Method::GET | Method::HEAD | Method::PUT | Method::DELETE)
+    }
+}
+
+/// Encode a request body as JSON.
+pub fn json_body<T: Serialize + ?Sized>(body: &T) -> Result<Option<Vec<u8>>, RequestError> {
+    Ok(Some(serde_json::to_vec(body)?))
+}
+
+impl LuckClient {
+    /// Call an endpoint, with the same retries, limits and instrumentation as every other call.
+    pub async fn execute<E: Endpoint>(&self, endpoint: E) -> Result<E::Response, RequestError> {
+        let path = endpoint.path();
+        let segments: Vec<&str> = path.iter().map(String::as_str).collect();
+        let mut url = self.url(&segments)?;
+        let query = endpoint.query();
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+
+        let mut request = self.request(endpoint.method(), url);
+        if let Some(body) = endpoint.body()? {
+            request = request.header(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+            request.body = Some(body);
+        }
+
+        let response = self.dispatch(request, endpoint.idempotent()).await?;
+        if response.body.is_empty() {
+            Ok(serde_json::from_slice(b"null")?)
+        } else {
+            response.json()
+        }
+    }
+}
+
+/// Treat a 404 as the absence of a resource.
+pub(crate) fn optional<T>(result: Result<T, RequestError>) -> Result<Option<T>, RequestError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(RequestError::NotFound) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
@@ -1,13 +1,115 @@
+use reqwest::header::InvalidHeaderValue;
+use reqwest::StatusCode;
+use crate::cassette::CassetteError;
+use crate::models::Health;
+use crate::transport::HttpResponse;
 use url::ParseError;
 
 #[derive(Debug, thiserror::Error)]
 pub enum RequestError {
     #[error("HTTP error: {0}")]
     Http(#[from] reqwest::Error),
+    #[error("Transport error: {0}")]
+    Transport(Box<dyn std::error::Error + Send + Sync>),
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
     #[error("URL error: {0}")]
     Url(#[from] ParseError),
+    /// The instance wasn't healthy before the timeout, with the outcome of the last health check.
+    #[error("LuckPerms instance did not become healthy in time{}", health_timeout_reason(.health.as_ref(), .error.as_deref()))]
+    HealthTimeout {
+        /// The last health report, if the instance reported itself as unhealthy.
+        health: Option<Health>,
+        /// The last error, if the health check failed.
+        #[source]
+        error: Option<Box<RequestError>>,
+    },
+    #[error("Bad request: {}", .message.as_deref().unwrap_or("no message"))]
+    BadRequest { message: Option<String> },
+    #[error("Unauthorized, check the API key")]
+    Unauthorized,
+    #[error("Forbidden")]
+    Forbidden,
+    #[error("Not found")]
+    NotFound,
+    #[error("Conflict: {}", .message.as_deref().unwrap_or("no message"))]
+    Conflict { message: Option<String> },
+    #[error("Server error {status}: {}", .message.as_deref().unwrap_or("no message"))]
+    Server { status: StatusCode, message: Option<String> },
+    #[error("Circuit breaker is open, the LuckPerms instance appears to be down")]
+    CircuitOpen,
+    #[error("Unexpected status {status}: {}", .message.as_deref().unwrap_or("no message"))]
+    Status { status: StatusCode, message: Option<String> },
+}
+
+impl RequestError {
+    /// Classify a non-success status and the error message the server sent with it.
+    pub fn from_status(status: StatusCode, message: Option<String>) -> Self {
+        match status {
+            StatusCode::BAD_REQUEST => Self::BadRequest { message },
+            StatusCode::UNAUTHORIZED => Self::Unauthorized,
+            StatusCode::FORBIDDEN => Self::Forbidden,
+            StatusCode::NOT_FOUND => Self::NotFound,
+            StatusCode::CONFLICT => Self::Conflict { message },
+            status if status.is_server_error() => Self::Server { status, message },
+            status => Self::Status { status, message },
+        }
+    }
+
+    /// The HTTP status the server responded with, if this error came from a response.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Http(e) => e.status(),
+            Self::BadRequest { .. } => Some(StatusCode::BAD_REQUEST),
+            Self::Unauthorized => Some(StatusCode::UNAUTHORIZED),
+            Self::Forbidden => Some(StatusCode::FORBIDDEN),
+            Self::NotFound => Some(StatusCode::NOT_FOUND),
+            Self::Conflict { .. } => Some(StatusCode::CONFLICT),
+            Self::Server { status, .. } | Self::Status { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
+
+fn health_timeout_reason(health: Option<&Health>, error: Option<&RequestError>) -> String {
+    match (health, error) {
+        (_, Some(error)) => format!(", last check failed: {}", error),
+        (Some(health), None) => match &health.details.reason {
+            Some(reason) => format!(", last reported unhealthy: {}", reason),
+            None => ", last reported unhealthy".to_string(),
+        },
+        (None, None) => String::new(),
+    }
+}
+
+/// Turn a non-success response into a [`RequestError`], keeping the server's error message.
+pub(crate) fn check_status(response: HttpResponse) -> Result<HttpResponse, RequestError> {
+    if response.status.is_success() {
+        return Ok(response);
+    }
+
+    Err(error_from_response(&response))
+}
+
+pub(crate) fn error_from_response(response: &HttpResponse) -> RequestError {
+    RequestError::from_status(response.status, error_message(&response.text()))
+}
+
+fn error_message(body: &str) -> Option<String> {
+    let body = body.trim();
+    if body.is_empty() {
+        return None;
+    }
+
+    if let Ok(serde_json::Value::Object(object)) = serde_json::from_str(body) {
+        for key in ["message", "error", "title"] {
+            if let Some(serde_json::Value::String(message)) = object.get(key) {
+                return Some(message.clone());
+            }
+        }
+    }
+
+    Some(body.to_string())
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -16,4 +118,50 @@ pub enum ClientCreationError {
     Http(#[from] reqwest::Error),
     #[error("URL error: {0}")]
     Url(#[from] ParseError),
-}
\ No newline at end of file
+    #[error("Invalid API key: {0}")]
+    InvalidApiKey(#[from] InvalidHeaderValue),
+    #[error("Cassette error: {0}")]
+    Cassette(#[from] CassetteError),
+    #[cfg(feature = "blocking")]
+    #[error("Runtime error: {0}")]
+    Runtime(std::io::Error),
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statuses_map_to_variants() {
+        let message = || Some("details".to_string());
+        assert!(matches!(RequestError::from_status(StatusCode::BAD_REQUEST, message()), RequestError::BadRequest { message: Some(_) }));
+        assert!(matches!(RequestError::from_status(StatusCode::UNAUTHORIZED, message()), RequestError::Unauthorized));
+        assert!(matches!(RequestError::from_status(StatusCode::FORBIDDEN, message()), RequestError::Forbidden));
+        assert!(matches!(RequestError::from_status(StatusCode::NOT_FOUND, message()), RequestError::NotFound));
+        assert!(matches!(RequestError::from_status(StatusCode::CONFLICT, message()), RequestError::Conflict { message: Some(_) }));
+        assert!(matches!(
+            RequestError::from_status(StatusCode::BAD_GATEWAY, message()),
+            RequestError::Server { status: StatusCode::BAD_GATEWAY, message: Some(_) },
+        ));
+        assert!(matches!(
+            RequestError::from_status(StatusCode::TOO_MANY_REQUESTS, None),
+            RequestError::Status { status: StatusCode::TOO_MANY_REQUESTS, message: None },
+        ));
+    }
+
+    #[test]
+    fn status_round_trips() {
+        for status in [StatusCode::BAD_REQUEST, StatusCode::NOT_FOUND, StatusCode::CONFLICT, StatusCode::SERVICE_UNAVAILABLE, StatusCode::IM_A_TEAPOT] {
+            assert_eq!(RequestError::from_status(status, None).status(), Some(status));
+        }
+    }
+
+    #[test]
+    fn error_messages_from_bodies() {
+        assert_eq!(error_message(r#"{"message":"group already exists"}"#).as_deref(), Some("group already exists"));
+        assert_eq!(error_message(r#"{"error":"Not Found","status":404}"#).as_deref(), Some("Not Found"));
+        assert_eq!(error_message(r#"{"title":"Bad Request"}"#).as_deref(), Some("Bad Request"));
+        assert_eq!(error_message("  Bad Gateway\n").as_deref(), Some("Bad Gateway"));
+        assert_eq!(error_message(r#"{"code":5}"#).as_deref(), Some(r#"{"code":5}"#));
+        assert_eq!(error_message(" \n"), None);
+    }
+}
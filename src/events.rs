@@ -0,0 +1,420 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use futures_util::stream::{self, BoxStream, Stream, StreamExt};
+use reqwest::header::{HeaderName, HeaderValue, ACCEPT};
+use reqwest::{Method, Url};
+use crate::errors::{error_from_response, RequestError};
+use crate::LuckClient;
+use crate::models::{CustomMessage, LogBroadcast, PostNetworkSync, PreNetworkSync};
+use crate::transport::Transport;
+
+/// A stream of events pushed by the LuckPerms instance.
+pub type EventStream = Pin<Box<dyn Stream<Item = Result<Event, RequestError>> + Send>>;
+
+/// An event received from one of the `/event` endpoints.
+#[derive(Debug, Clone)]
+pub enum Event {
+    LogBroadcast(LogBroadcast),
+    PreNetworkSync(PreNetworkSync),
+    PostNetworkSync(PostNetworkSync),
+    PreSync,
+    PostSync,
+    CustomMessage(CustomMessage),
+    /// The connection for an event kind was lost and later re-established.
+    /// Events pushed in between may have been missed.
+    Gap(EventGap),
+}
+
+/// A period during which an event subscription was disconnected.
+#[derive(Debug, Clone)]
+pub struct EventGap {
+    pub kind: EventKind,
+    pub disconnected_at: SystemTime,
+    pub reconnected_at: SystemTime,
+    /// The `Last-Event-ID` sent when reconnecting, if the server provided event ids.
+    pub resumed_from: Option<String>,
+}
+
+/// Options controlling how a reconnecting event subscription backs off.
+#[derive(Debug, Clone)]
+pub struct ReconnectOptions {
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Yield the connection error after this many consecutive failed attempts, and again
+    /// after every further `max_attempts` failures. The subscription keeps retrying, backing
+    /// off further each time, for as long as the stream is polled.
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for ReconnectOptions {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_attempts: None,
+        }
+    }
+}
+
+impl ReconnectOptions {
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+/// The kinds of events that can be subscribed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    LogBroadcast,
+    PreNetworkSync,
+    PostNetworkSync,
+    PreSync,
+    PostSync,
+    CustomMessage,
+}
+
+impl EventKind {
+    pub const ALL: [EventKind; 6] = [
+        Self::LogBroadcast,
+        Self::PreNetworkSync,
+        Self::PostNetworkSync,
+        Self::PreSync,
+        Self::PostSync,
+        Self::CustomMessage,
+    ];
+
+    /// The name of the `/event/{name}` endpoint that streams this kind of event.
+    pub fn endpoint(&self) -> &'static str {
+        match self {
+            Self::LogBroadcast => "log-broadcast",
+            Self::PreNetworkSync => "pre-network-sync",
+            Self::PostNetworkSync => "post-network-sync",
+            Self::PreSync => "pre-sync",
+            Self::PostSync => "post-sync",
+            Self::CustomMessage => "custom-message-receive",
+        }
+    }
+
+    pub(crate) fn decode(&self, data: &str) -> Result<Event, RequestError> {
+        Ok(match self {
+            Self::LogBroadcast => Event::LogBroadcast(serde_json::from_str(data)?),
+            Self::PreNetworkSync => Event::PreNetworkSync(serde_json::from_str(data)?),
+            Self::PostNetworkSync => Event::PostNetworkSync(serde_json::from_str(data)?),
+            Self::PreSync => Event::PreSync,
+            Self::PostSync => Event::PostSync,
+            Self::CustomMessage => Event::CustomMessage(serde_json::from_str(data)?),
+        })
+    }
+}
+
+impl LuckClient {
+    /// Subscribe to every event the LuckPerms instance pushes.
+    pub async fn events(&self) -> Result<EventStream, RequestError> {
+        self.events_of(&EventKind::ALL).await
+    }
+
+    /// Subscribe to a specific set of event kinds.
+    pub async fn events_of(&self, kinds: &[EventKind]) -> Result<EventStream, RequestError> {
+        let mut streams = Vec::with_capacity(kinds.len());
+
+        for kind in kinds.iter().copied() {
+            let messages = open_event_stream(self.inner.transport.as_ref(), &self.inner.api_key, &self.inner.base_url, kind, None).await?;
+            streams.push(messages.filter_map(move |message| async move {
+                match message {
+                    Ok(message) if message.data.is_empty() => None,
+                    Ok(message) => Some(kind.decode(&message.data)),
+                    Err(e) => Some(Err(e)),
+                }
+            }).boxed());
+        }
+
+        Ok(stream::select_all(streams).boxed())
+    }
+
+    /// Subscribe to a set of event kinds, reconnecting whenever a connection drops.
+    ///
+    /// Reconnects resume from the last received event id where the server provides one,
+    /// and every disconnected period is reported as an [`Event::Gap`].
+    pub fn reconnecting_events(&self, kinds: &[EventKind], options: ReconnectOptions) -> EventStream {
+        let streams = kinds.iter().copied().map(|kind| {
+            reconnecting_stream(self.inner.transport.clone(), self.inner.api_key.clone(), self.inner.base_url.clone(), kind, options.clone())
+        });
+
+        stream::select_all(streams).boxed()
+    }
+}
+
+async fn open_event_stream(transport: &dyn Transport, api_key: &HeaderValue, base_url: &Url, kind: EventKind, last_event_id: Option<&str>) -> Result<impl Stream<Item = Result<SseMessage, RequestError>>, RequestError> {
+    let url = crate::endpoint_url(base_url, &["event", kind.endpoint()])?;
+    let mut request = crate::request(api_key, Method::GET, url)
+        .header(ACCEPT, HeaderValue::from_static("text/event-stream"));
+    if let Some(id) = last_event_id.and_then(|id| HeaderValue::from_str(id).ok()) {
+        request = request.header(HeaderName::from_static("last-event-id"), id);
+    }
+
+    let response = transport.stream(request).await?;
+    if !response.status.is_success() {
+        return Err(error_from_response(&response.collect().await?));
+    }
+
+    Ok(sse_messages(response.body))
+}
+
+struct Reconnecting {
+    transport: Arc<dyn Transport>,
+    api_key: HeaderValue,
+    base_url: Url,
+    kind: EventKind,
+    options: ReconnectOptions,
+    messages: Option<BoxStream<'static, Result<SseMessage, RequestError>>>,
+    last_event_id: Option<String>,
+    disconnected_at: Option<SystemTime>,
+    server_retry: Option<Duration>,
+    attempt: u32,
+}
+
+fn reconnecting_stream(transport: Arc<dyn Transport>, api_key: HeaderValue, base_url: Url, kind: EventKind, options: ReconnectOptions) -> EventStream {
+    let state = Reconnecting {
+        transport,
+        api_key,
+        base_url,
+        kind,
+        options,
+        messages: None,
+        last_event_id: None,
+        disconnected_at: None,
+        server_retry: None,
+        attempt: 0,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            let Some(messages) = state.messages.as_mut() else {
+                if state.attempt > 0 {
+                    // The server's `retry:` is a lower bound, repeated failures still back off.
+                    let backoff = state.options.backoff(state.attempt);
+                    let delay = state.server_retry.map_or(backoff, |retry| retry.max(backoff));
+                    tokio::time::sleep(delay).await;
+                }
+
+                match open_event_stream(state.transport.as_ref(), &state.api_key, &state.base_url, state.kind, state.last_event_id.as_deref()).await {
+                    Ok(messages) => {
+                        state.messages = Some(messages.boxed());
+                        state.attempt = 0;
+                        if let Some(disconnected_at) = state.disconnected_at.take() {
+                            let gap = EventGap {
+                                kind: state.kind,
+                                disconnected_at,
+                                reconnected_at: SystemTime::now(),
+                                resumed_from: state.last_event_id.clone(),
+                            };
+                            return Some((Ok(Event::Gap(gap)), state));
+                        }
+                    },
+                    Err(e) => {
+                        state.attempt += 1;
+                        if state.options.max_attempts.is_some_and(|max| state.attempt % max.max(1) == 0) {
+                            return Some((Err(e), state));
+                        }
+                    },
+                }
+                continue;
+            };
+
+            match messages.next().await {
+                Some(Ok(message)) => {
+                    if let Some(id) = message.id {
+                        state.last_event_id = Some(id);
+                    }
+                    if let Some(retry) = message.retry {
+                        state.server_retry = Some(Duration::from_millis(retry));
+                    }
+                    if message.data.is_empty() {
+                        continue;
+                    }
+                    let event = state.kind.decode(&message.data);
+                    return Some((event, state));
+                },
+                Some(Err(_)) | None => {
+                    state.messages = None;
+                    state.attempt = 1;
+                    state.disconnected_at.get_or_insert_with(SystemTime::now);
+                },
+            }
+        }
+    }).boxed()
+}
+
+/// A single message of a `text/event-stream` body.
+#[derive(Debug, Clone, Default)]
+struct SseMessage {
+    pub id: Option<String>,
+    pub event: Option<String>,
+    pub data: String,
+    pub retry: Option<u64>,
+}
+
+fn sse_messages(body: BoxStream<'static, Result<Vec<u8>, RequestError>>) -> impl Stream<Item = Result<SseMessage, RequestError>> {
+    let state = (body, SseParser::default(), false);
+    stream::unfold(state, |(mut body, mut parser, done)| async move {
+        if done {
+            return None;
+        }
+
+        loop {
+            if let Some(message) = parser.next_message() {
+                return Some((Ok(message), (body, parser, false)));
+            }
+
+            match body.next().await {
+                Some(Ok(chunk)) => parser.feed(&chunk),
+                Some(Err(e)) => return Some((Err(e), (body, parser, true))),
+                None => return None,
+            }
+        }
+    })
+}
+
+/// Incremental parser for the `text/event-stream` format.
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    current: SseMessage,
+    has_data: bool,
+}
+
+impl SseParser {
+    fn feed(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    fn next_message(&mut self) -> Option<SseMessage> {
+        while let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+            let mut line: Vec<u8> = self.buffer.drain(..=end).collect();
+            line.pop();
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            let line = String::from_utf8_lossy(&line);
+
+            if line.is_empty() {
+                let message = std::mem::take(&mut self.current);
+                // Messages without data still carry an id or reconnection time to apply.
+                if std::mem::take(&mut self.has_data) || message.id.is_some() || message.retry.is_some() {
+                    return Some(message);
+                }
+                continue;
+            }
+
+            if line.starts_with(':') {
+                continue;
+            }
+
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line.as_ref(), ""),
+            };
+
+            match field {
+                "data" => {
+                    if self.has_data {
+                        self.current.data.push('\n');
+                    }
+                    self.current.data.push_str(value);
+                    self.has_data = true;
+                },
+                "event" => self.current.event = Some(value.to_string()),
+                "id" => self.current.id = Some(value.to_string()),
+                "retry" => self.current.retry = value.parse().ok(),
+                _ => {},
+            }
+        }
+
+        None
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(chunks: &[&str]) -> Vec<SseMessage> {
+        let mut parser = SseParser::default();
+        let mut messages = Vec::new();
+        for chunk in chunks {
+            parser.feed(chunk.as_bytes());
+            while let Some(message) = parser.next_message() {
+                messages.push(message);
+            }
+        }
+        messages
+    }
+
+    #[test]
+    fn sse_data_split_across_chunks() {
+        let messages = parse(&["da", "ta: {\"a\":", "1}\n", "\n"]);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].data, "{\"a\":1}");
+    }
+
+    #[test]
+    fn sse_crlf_line_endings() {
+        let messages = parse(&["event: ping\r\ndata: one\r\n\r\ndata: two\r", "\n\r\n"]);
+        let data: Vec<&str> = messages.iter().map(|message| message.data.as_str()).collect();
+        assert_eq!(data, ["one", "two"]);
+        assert_eq!(messages[0].event.as_deref(), Some("ping"));
+    }
+
+    #[test]
+    fn sse_multi_line_data() {
+        let messages = parse(&["data: first\ndata:second\ndata\n\n"]);
+        assert_eq!(messages[0].data, "first\nsecond\n");
+    }
+
+    #[test]
+    fn sse_comments_are_ignored() {
+        let messages = parse(&[": keep-alive\n\n", ":another\ndata: x\n\n"]);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].data, "x");
+    }
+
+    #[test]
+    fn sse_id_only_message() {
+        let messages = parse(&["id: 42\n\n"]);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].id.as_deref(), Some("42"));
+        assert!(messages[0].data.is_empty());
+    }
+
+    #[test]
+    fn sse_retry() {
+        let messages = parse(&["retry: 1500\n\nretry: soon\ndata: x\n\nretry: soon\n\n"]);
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].retry, Some(1500));
+        assert!(messages[0].data.is_empty());
+        assert_eq!(messages[1].retry, None);
+    }
+
+    #[test]
+    fn decode_log_broadcast() {
+        let data = r#"{
+            "entry": {
+                "timestamp": 1693838535,
+                "source": {"uniqueId": "069a79f4-44e9-4726-a5be-fca90e38aaf5", "name": "Notch"},
+                "target": {"type": "user", "uniqueId": "853c80ef-3c37-49fd-aa49-938b674adae6", "name": "jeb_"},
+                "description": "permission set luckperms.user.info true"
+            },
+            "origin": "LOCAL"
+        }"#;
+
+        let Event::LogBroadcast(broadcast) = EventKind::LogBroadcast.decode(data).unwrap() else {
+            panic!("expected a log broadcast");
+        };
+        assert_eq!(broadcast.entry.timestamp, Some(1693838535));
+        assert_eq!(broadcast.entry.source.name, "Notch");
+        assert_eq!(broadcast.entry.target.name, "jeb_");
+        assert_eq!(broadcast.entry.description, "permission set luckperms.user.info true");
+        assert!(matches!(broadcast.origin, crate::models::LogBroadcastOrigin::Local));
+    }
+}
@@ -1,123 +1,174 @@
-use reqwest::StatusCode;
-use crate::errors::RequestError;
+use reqwest::Method;
+use serde::de::IgnoredAny;
 use crate::LuckClient;
-use crate::models::{GroupCreateRequest, Group, GroupSearchResult, Node, Metadata, PermissionCheckResult};
+use crate::holders::HolderRef;
+use crate::endpoint::{json_body, optional, Endpoint};
+use crate::errors::RequestError;
+use crate::models::{GroupCreateRequest, Group, GroupSearchResult, Node, Metadata, PermissionCheckResult, TemporaryMergeStrategy};
 use crate::requests::{PermissionCheckRequest, SearchRequest};
 
+/// `GET /group`, the names of all groups.
+#[derive(Debug, Clone)]
+pub struct ListGroups;
+
+impl Endpoint for ListGroups {
+    type Response = Vec<String>;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["group".to_string()]
+    }
+}
+
+/// `POST /group`, create a group.
+#[derive(Debug, Clone)]
+pub struct CreateGroup {
+    pub name: String,
+}
+
+impl Endpoint for CreateGroup {
+    type Response = Group;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["group".to_string()]
+    }
+
+    fn body(&self) -> Result<Option<Vec<u8>>, RequestError> {
+        json_body(&GroupCreateRequest {
+            name: self.name.clone(),
+        })
+    }
+}
+
+/// `GET /group/search`, find groups with matching nodes.
+#[derive(Debug, Clone)]
+pub struct SearchGroups {
+    pub search: SearchRequest,
+}
+
+impl Endpoint for SearchGroups {
+    type Response = Vec<GroupSearchResult>;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["group".to_string(), "search".to_string()]
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        self.search.query_pairs()
+    }
+}
+
+/// `GET /group/{name}`.
+#[derive(Debug, Clone)]
+pub struct GetGroup {
+    pub name: String,
+}
+
+impl Endpoint for GetGroup {
+    type Response = Group;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["group".to_string(), self.name.clone()]
+    }
+}
+
+/// `DELETE /group/{name}`.
+#[derive(Debug, Clone)]
+pub struct DeleteGroup {
+    pub name: String,
+}
+
+impl Endpoint for DeleteGroup {
+    type Response = IgnoredAny;
+
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["group".to_string(), self.name.clone()]
+    }
+}
+
 impl LuckClient {
     /// Get all group names.
     pub async fn get_groups(&self) -> Result<Vec<String>, RequestError> {
-        let url = self.base_url.join("/group")?;
-        let response = self.client.get(url).send().await?;
-        let groups: Vec<String> = response.json().await?;
-        Ok(groups)
+        self.execute(ListGroups).await
     }
 
     /// Create a new group.
     pub async fn create_group(&self, name: String) -> Result<Group, RequestError> {
-        let url = self.base_url.join("/group")?;
-        let response = self.client.post(url).json(&GroupCreateRequest {name}).send().await?;
-        Ok(response.error_for_status()?.json().await?)
+        self.execute(CreateGroup { name }).await
     }
 
     /// Search for a group matching nodes.
     pub async fn search_group(&self, search_request: SearchRequest) -> Result<Vec<GroupSearchResult>, RequestError> {
-        let mut url = self.base_url.join("/group/search")?;
-        let query = url.query_pairs_mut();
-        search_request.set_query_values(query);
-        let response = self.client.get(url).send().await?;
-        let groups: Vec<GroupSearchResult> = response.error_for_status()?.json().await?;
-
-        Ok(groups)
+        self.execute(SearchGroups { search: search_request }).await
     }
 
     /// Get a group based on its name.
     pub async fn get_group(&self, name: String) -> Result<Option<Group>, RequestError> {
-        let url = self.base_url.join(&format!("/group/{}", name))?;
-        let response = self.client.get(url).send().await?;
-
-        let status = response.status();
-
-        if status == StatusCode::NOT_FOUND {
-            return Ok(None);
-        }
-
-        let group: Group = response.error_for_status()?.json().await?;
-
-        Ok(Some(group))
+        optional(self.execute(GetGroup { name }).await)
     }
 
     /// Delete a group based on its name.
     pub async fn delete_group(&self, name: String) -> Result<(), RequestError> {
-        let url = self.base_url.join(&format!("/group/{}", name))?;
-        let response = self.client.delete(url).send().await?;
-        response.error_for_status()?;
+        self.execute(DeleteGroup { name }).await?;
         Ok(())
     }
 
     /// Get a group's notes.
     pub async fn get_group_nodes(&self, name: String) -> Result<Vec<Node>, RequestError> {
-        let url = self.base_url.join(&format!("/group/{}/nodes", name))?;
-        let response = self.client.get(url).send().await?;
-        let nodes: Vec<Node> = response.json().await?;
-        Ok(nodes)
+        HolderRef::Group(name).get_nodes(self).await
     }
 
     /// Add a node to a group.
-    pub async fn add_group_node(&self, name: String, node: Node) -> Result<Vec<Node>, RequestError> {
-        let url = self.base_url.join(&format!("/group/{}/nodes", name))?;
-        let response = self.client.post(url).json(&node).send().await?
-            .error_for_status()?;
-        Ok(response.json().await?)
+    pub async fn add_group_node(&self, name: String, node: Node, merge_strategy: TemporaryMergeStrategy) -> Result<Vec<Node>, RequestError> {
+        HolderRef::Group(name).add_node(self, node, merge_strategy).await
     }
 
     /// Add multiple nodes to a group.
-    pub async fn add_group_nodes(&self, name: String, nodes: Vec<Node>) -> Result<Vec<Node>, RequestError> {
-        let url = self.base_url.join(&format!("/group/{}/nodes", name))?;
-        let response = self.client.patch(url).json(&nodes).send().await?
-            .error_for_status()?;
-        Ok(response.json().await?)
+    pub async fn add_group_nodes(&self, name: String, nodes: Vec<Node>, merge_strategy: TemporaryMergeStrategy) -> Result<Vec<Node>, RequestError> {
+        HolderRef::Group(name).add_nodes(self, nodes, merge_strategy).await
     }
 
     /// Set nodes for a group.
     pub async fn set_group_nodes(&self, name: String, nodes: Vec<Node>) -> Result<(), RequestError> {
-        let url = self.base_url.join(&format!("/group/{}/nodes", name))?;
-        let response = self.client.put(url).json(&nodes).send().await?;
-        response.error_for_status()?;
-        Ok(())
+        HolderRef::Group(name).set_nodes(self, nodes).await
     }
 
     /// Delete nodes from a group.
     pub async fn delete_group_node(&self, name: String, nodes: Vec<Node>) -> Result<(), RequestError> {
-        let url = self.base_url.join(&format!("/group/{}/nodes", name))?;
-        let response = self.client.delete(url).json(&nodes).send().await?;
-        response.error_for_status()?;
-        Ok(())
+        HolderRef::Group(name).delete_nodes(self, nodes).await
     }
 
     /// Get a group's metadata.
     pub async fn get_group_metadata(&self, name: String) -> Result<Metadata, RequestError> {
-        let url = self.base_url.join(&format!("/group/{}/meta", name))?;
-        let response = self.client.get(url).send().await?;
-        let meta: Metadata = response.json().await?;
-        Ok(meta)
+        HolderRef::Group(name).metadata(self).await
     }
 
     /// Check if a group has a permission.
     pub async fn check_group_permission(&self, name: String, permission: String) -> Result<PermissionCheckResult, RequestError> {
-        let mut url = self.base_url.join(&format!("/group/{}/permissionCheck", name))?;
-        url.query_pairs_mut().append_pair("permission", &permission);
-        let response = self.client.get(url).send().await?;
-        let result: PermissionCheckResult = response.json().await?;
-        Ok(result)
+        HolderRef::Group(name).check_permission(self, permission).await
     }
 
     /// Check if a group has a permission with advanced query settings.
     pub async fn check_group_permission_query(&self, name: String, request: PermissionCheckRequest) -> Result<PermissionCheckResult, RequestError> {
-        let url = self.base_url.join(&format!("/group/{}/permissionCheck", name))?;
-        let response = self.client.post(url).json(&request).send().await?;
-        let result: PermissionCheckResult = response.error_for_status()?.json().await?;
-
-        Ok(result)
+        HolderRef::Group(name).check_permission_query(self, request).await
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,57 @@
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+use tokio::time::Instant;
+use crate::errors::{error_from_response, RequestError};
+use crate::LuckClient;
+use crate::models::Health;
+
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+impl LuckClient {
+    /// Get the health of the LuckPerms instance.
+    pub async fn health(&self) -> Result<Health, RequestError> {
+        let url = self.url(&["health"])?;
+        // Not sent through `dispatch`, an unhealthy instance responds with 503 and a health report.
+        let response = self.send_once(self.request(Method::GET, url)).await?;
+        if response.status.is_success() {
+            return response.json();
+        }
+
+        match response.json::<Health>() {
+            Ok(health) if response.status == StatusCode::SERVICE_UNAVAILABLE => Ok(health),
+            _ => Err(error_from_response(&response)),
+        }
+    }
+
+    /// Wait until the LuckPerms instance reports itself as healthy.
+    ///
+    /// Connection errors are treated as "not ready yet" until the timeout elapses, which
+    /// also bounds every single check. On timeout the outcome of the last check is returned
+    /// in [`RequestError::HealthTimeout`]. Client errors such as [`RequestError::Unauthorized`]
+    /// won't go away by waiting, so they are returned straight away.
+    pub async fn wait_until_healthy(&self, timeout: Duration) -> Result<Health, RequestError> {
+        let deadline = Instant::now().checked_add(timeout);
+
+        loop {
+            let remaining = deadline.map_or(Duration::MAX, |deadline| deadline.saturating_duration_since(Instant::now()));
+            let (health, error) = match tokio::time::timeout(remaining, self.health()).await {
+                Ok(Ok(health)) if health.healthy => return Ok(health),
+                Ok(Ok(health)) => (Some(health), None),
+                Ok(Err(e)) if is_client_error(&e) => return Err(e),
+                Ok(Err(e)) => (None, Some(Box::new(e))),
+                Err(elapsed) => (None, Some(Box::new(RequestError::Transport(elapsed.into())))),
+            };
+
+            if deadline.is_some_and(|deadline| Instant::now() + HEALTH_POLL_INTERVAL > deadline) {
+                return Err(RequestError::HealthTimeout { health, error });
+            }
+
+            tokio::time::sleep(HEALTH_POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// Whether the instance rejected the check itself, e.g. because of a wrong API key or base URL.
+fn is_client_error(error: &RequestError) -> bool {
+    error.status().is_some_and(|status| status.is_client_error() && status != StatusCode::TOO_MANY_REQUESTS)
+}
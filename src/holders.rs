@@ -0,0 +1,277 @@
+use reqwest::Method;
+use serde::de::IgnoredAny;
+use uuid::Uuid;
+use crate::LuckClient;
+use crate::endpoint::{json_body, Endpoint};
+use crate::errors::RequestError;
+use crate::models::{Metadata, Node, PermissionCheckResult, TemporaryMergeStrategy};
+use crate::requests::PermissionCheckRequest;
+
+/// Something that holds nodes, either a user or a group.
+///
+/// ```rust
+/// use luckperms_rs::holders::HolderRef;
+/// use luckperms_rs::LuckClient;
+///
+/// # async fn run(client: LuckClient) {
+/// for holder in [HolderRef::User(uuid::Uuid::nil()), HolderRef::Group("admin".to_string())] {
+///     let nodes = holder.get_nodes(&client).await.unwrap();
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HolderRef {
+    User(Uuid),
+    Group(String),
+}
+
+impl HolderRef {
+    /// The path of an endpoint of the holder, e.g. `["user", uuid, "nodes"]`.
+    fn path(&self, endpoint: &str) -> Vec<String> {
+        match self {
+            Self::User(uuid) => vec!["user".to_string(), uuid.to_string(), endpoint.to_string()],
+            Self::Group(name) => vec!["group".to_string(), name.clone(), endpoint.to_string()],
+        }
+    }
+
+    /// Get all nodes of the holder.
+    pub async fn get_nodes(&self, client: &LuckClient) -> Result<Vec<Node>, RequestError> {
+        client.execute(GetNodes { holder: self.clone() }).await
+    }
+
+    /// Add a node, returning the holder's nodes.
+    pub async fn add_node(&self, client: &LuckClient, node: Node, merge_strategy: TemporaryMergeStrategy) -> Result<Vec<Node>, RequestError> {
+        client.execute(AddNode { holder: self.clone(), node, merge_strategy }).await
+    }
+
+    /// Add multiple nodes, returning the holder's nodes.
+    pub async fn add_nodes(&self, client: &LuckClient, nodes: Vec<Node>, merge_strategy: TemporaryMergeStrategy) -> Result<Vec<Node>, RequestError> {
+        client.execute(AddNodes { holder: self.clone(), nodes, merge_strategy }).await
+    }
+
+    /// Replace all nodes of the holder.
+    pub async fn set_nodes(&self, client: &LuckClient, nodes: Vec<Node>) -> Result<(), RequestError> {
+        client.execute(SetNodes { holder: self.clone(), nodes }).await?;
+        Ok(())
+    }
+
+    /// Delete nodes from the holder.
+    pub async fn delete_nodes(&self, client: &LuckClient, nodes: Vec<Node>) -> Result<(), RequestError> {
+        client.execute(DeleteNodes { holder: self.clone(), nodes }).await?;
+        Ok(())
+    }
+
+    /// Get the holder's metadata.
+    pub async fn metadata(&self, client: &LuckClient) -> Result<Metadata, RequestError> {
+        client.execute(GetMetadata { holder: self.clone() }).await
+    }
+
+    /// Check if the holder has a permission, in the client's default contexts if it has any.
+    pub async fn check_permission(&self, client: &LuckClient, permission: String) -> Result<PermissionCheckResult, RequestError> {
+        if !client.default_contexts().is_empty() {
+            return self.check_permission_query(client, PermissionCheckRequest::new(permission)).await;
+        }
+
+        client.execute(CheckPermission { holder: self.clone(), permission }).await
+    }
+
+    /// Check if the holder has a permission with more specific query options.
+    pub async fn check_permission_query(&self, client: &LuckClient, mut request: PermissionCheckRequest) -> Result<PermissionCheckResult, RequestError> {
+        client.apply_default_contexts(&mut request);
+        client.execute(CheckPermissionQuery { holder: self.clone(), request }).await
+    }
+}
+
+impl From<Uuid> for HolderRef {
+    fn from(uuid: Uuid) -> Self {
+        Self::User(uuid)
+    }
+}
+
+/// `GET /{user|group}/{id}/nodes`.
+#[derive(Debug, Clone)]
+pub struct GetNodes {
+    pub holder: HolderRef,
+}
+
+impl Endpoint for GetNodes {
+    type Response = Vec<Node>;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path(&self) -> Vec<String> {
+        self.holder.path("nodes")
+    }
+}
+
+/// `POST /{user|group}/{id}/nodes`, add a node and return the holder's nodes.
+#[derive(Debug, Clone)]
+pub struct AddNode {
+    pub holder: HolderRef,
+    pub node: Node,
+    pub merge_strategy: TemporaryMergeStrategy,
+}
+
+impl Endpoint for AddNode {
+    type Response = Vec<Node>;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path(&self) -> Vec<String> {
+        self.holder.path("nodes")
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        vec![("temporaryNodeMergeStrategy", self.merge_strategy.to_string())]
+    }
+
+    fn body(&self) -> Result<Option<Vec<u8>>, RequestError> {
+        json_body(&self.node)
+    }
+}
+
+/// `PATCH /{user|group}/{id}/nodes`, add nodes and return the holder's nodes.
+#[derive(Debug, Clone)]
+pub struct AddNodes {
+    pub holder: HolderRef,
+    pub nodes: Vec<Node>,
+    pub merge_strategy: TemporaryMergeStrategy,
+}
+
+impl Endpoint for AddNodes {
+    type Response = Vec<Node>;
+
+    fn method(&self) -> Method {
+        Method::PATCH
+    }
+
+    fn path(&self) -> Vec<String> {
+        self.holder.path("nodes")
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        vec![("temporaryNodeMergeStrategy", self.merge_strategy.to_string())]
+    }
+
+    fn body(&self) -> Result<Option<Vec<u8>>, RequestError> {
+        json_body(&self.nodes)
+    }
+}
+
+/// `PUT /{user|group}/{id}/nodes`, replace all of the holder's nodes.
+#[derive(Debug, Clone)]
+pub struct SetNodes {
+    pub holder: HolderRef,
+    pub nodes: Vec<Node>,
+}
+
+impl Endpoint for SetNodes {
+    type Response = IgnoredAny;
+
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn path(&self) -> Vec<String> {
+        self.holder.path("nodes")
+    }
+
+    fn body(&self) -> Result<Option<Vec<u8>>, RequestError> {
+        json_body(&self.nodes)
+    }
+}
+
+/// `DELETE /{user|group}/{id}/nodes`, remove nodes from the holder.
+#[derive(Debug, Clone)]
+pub struct DeleteNodes {
+    pub holder: HolderRef,
+    pub nodes: Vec<Node>,
+}
+
+impl Endpoint for DeleteNodes {
+    type Response = IgnoredAny;
+
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn path(&self) -> Vec<String> {
+        self.holder.path("nodes")
+    }
+
+    fn body(&self) -> Result<Option<Vec<u8>>, RequestError> {
+        json_body(&self.nodes)
+    }
+}
+
+/// `GET /{user|group}/{id}/meta`.
+#[derive(Debug, Clone)]
+pub struct GetMetadata {
+    pub holder: HolderRef,
+}
+
+impl Endpoint for GetMetadata {
+    type Response = Metadata;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path(&self) -> Vec<String> {
+        self.holder.path("meta")
+    }
+}
+
+/// `GET /{user|group}/{id}/permissionCheck`, check a permission with the default query options.
+#[derive(Debug, Clone)]
+pub struct CheckPermission {
+    pub holder: HolderRef,
+    pub permission: String,
+}
+
+impl Endpoint for CheckPermission {
+    type Response = PermissionCheckResult;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path(&self) -> Vec<String> {
+        self.holder.path("permissionCheck")
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        vec![("permission", self.permission.clone())]
+    }
+}
+
+/// `POST /{user|group}/{id}/permissionCheck`, check a permission with custom query options.
+#[derive(Debug)]
+pub struct CheckPermissionQuery {
+    pub holder: HolderRef,
+    pub request: PermissionCheckRequest,
+}
+
+impl Endpoint for CheckPermissionQuery {
+    type Response = PermissionCheckResult;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path(&self) -> Vec<String> {
+        self.holder.path("permissionCheck")
+    }
+
+    fn body(&self) -> Result<Option<Vec<u8>>, RequestError> {
+        json_body(&self.request)
+    }
+
+    fn idempotent(&self) -> bool {
+        true
+    }
+}
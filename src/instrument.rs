@@ -0,0 +1,64 @@
+use std::time::Duration;
+use tracing::field::Empty;
+use tracing::Span;
+use crate::errors::RequestError;
+use crate::transport::{HttpRequest, HttpResponse};
+
+/// The span of a single API call, including any retries.
+pub(crate) struct RequestTrace {
+    span: Span,
+    log_payloads: bool,
+}
+
+impl RequestTrace {
+    pub(crate) fn start(request: &HttpRequest, endpoint: &str, target: Option<&str>, log_payloads: bool) -> Self {
+        let span = tracing::info_span!(
+            "luckperms_request",
+            method = %request.method,
+            endpoint = %endpoint,
+            target,
+            status = Empty,
+            duration_ms = Empty,
+            attempts = Empty,
+        );
+
+        if let Some(body) = &request.body {
+            if log_payloads {
+                tracing::trace!(parent: &span, body = %String::from_utf8_lossy(body), "request payload");
+            } else {
+                tracing::trace!(parent: &span, bytes = body.len(), "request payload redacted");
+            }
+        }
+
+        Self { span, log_payloads }
+    }
+
+    pub(crate) fn span(&self) -> Span {
+        self.span.clone()
+    }
+
+    pub(crate) fn finish(self, result: &Result<HttpResponse, RequestError>, attempts: u32, duration: Duration) {
+        let status = match result {
+            Ok(response) => Some(response.status),
+            Err(e) => e.status(),
+        };
+        if let Some(status) = status {
+            self.span.record("status", status.as_u16());
+        }
+        self.span.record("duration_ms", duration.as_millis() as u64);
+        self.span.record("attempts", attempts);
+
+        match result {
+            Ok(response) if self.log_payloads => {
+                tracing::trace!(parent: &self.span, body = %response.text(), "response payload");
+                tracing::debug!(parent: &self.span, "request succeeded");
+            },
+            Ok(_) => tracing::debug!(parent: &self.span, "request succeeded"),
+            // Client errors are often expected, e.g. a 404 that becomes `Ok(None)`.
+            Err(e) if matches!(e.status(), Some(status) if status.is_client_error()) => {
+                tracing::debug!(parent: &self.span, error = %e, "request failed");
+            },
+            Err(e) => tracing::warn!(parent: &self.span, error = %e, "request failed"),
+        }
+    }
+}
@@ -1,6 +1,17 @@
-use reqwest::header::HeaderMap;
-use reqwest::{Client, Url};
-use crate::errors::ClientCreationError;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::{Method, Url};
+use crate::breaker::{CircuitBreaker, CircuitState, Permit};
+use crate::builder::LuckClientBuilder;
+use crate::errors::{check_status, ClientCreationError, RequestError};
+use crate::limiter::Limiter;
+use crate::metrics::{MetricsRecorder, RequestMetrics};
+use crate::models::ContextSet;
+use crate::requests::PermissionCheckRequest;
+use crate::retry::RetryPolicy;
+use crate::transport::{HttpRequest, HttpResponse, Transport};
 
 pub mod users;
 pub mod models;
@@ -8,11 +19,49 @@ pub mod errors;
 pub mod requests;
 pub mod groups;
 pub mod actions;
+pub mod tracks;
+pub mod events;
+pub mod messaging;
+pub mod health;
+pub mod builder;
+pub mod retry;
+pub mod breaker;
+pub mod transport;
+pub mod endpoint;
+pub mod holders;
+pub mod cassette;
+pub mod metrics;
+pub mod middleware;
+pub mod limiter;
+#[cfg(feature = "tracing")]
+mod instrument;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+const API_KEY_HEADER: HeaderName = HeaderName::from_static("x-api-key");
 
 /// A client for interacting with a LuckPerms instance.
+///
+/// Cloning is cheap, clones share their connection pool, limiter, circuit breaker and other state.
+#[derive(Clone)]
 pub struct LuckClient {
+    inner: Arc<ClientInner>,
+}
+
+#[derive(Clone)]
+struct ClientInner {
     base_url: Url,
-    client: Client,
+    api_key: HeaderValue,
+    default_contexts: ContextSet,
+    transport: Arc<dyn Transport>,
+    retry_policy: Arc<dyn RetryPolicy>,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    metrics_recorder: Option<Arc<dyn MetricsRecorder>>,
+    limiter: Option<Arc<Limiter>>,
+    #[cfg(feature = "tracing")]
+    log_payloads: bool,
 }
 
 impl LuckClient {
@@ -24,17 +73,293 @@ impl LuckClient {
     ///     let client = LuckClient::try_new("http://localhost:8080".to_string(), "YOUR API KEY".to_string()).unwrap();
     /// }
     pub fn try_new(base_url: String, api_key: String) -> Result<Self, ClientCreationError> {
-        let url = Url::parse(&base_url)?;
+        LuckClientBuilder::new(base_url, api_key).build()
+    }
+
+    /// Create a builder to configure timeouts, TLS, an existing `reqwest::Client` or a custom transport.
+    pub fn builder(base_url: String, api_key: String) -> LuckClientBuilder {
+        LuckClientBuilder::new(base_url, api_key)
+    }
+
+    /// Derive a client that authenticates with a different API key.
+    ///
+    /// The new client shares the connection pool and all other state with this one.
+    pub fn with_api_key(&self, api_key: String) -> Result<LuckClient, ClientCreationError> {
+        let api_key = HeaderValue::from_str(&api_key)?;
+        Ok(self.derive(|inner| inner.api_key = api_key))
+    }
+
+    /// Derive a client whose permission checks use these contexts unless a query sets its own.
+    ///
+    /// The new client shares the connection pool and all other state with this one.
+    pub fn with_default_contexts(&self, contexts: ContextSet) -> LuckClient {
+        self.derive(|inner| inner.default_contexts = contexts)
+    }
+
+    /// The contexts permission checks use unless a query sets its own.
+    pub fn default_contexts(&self) -> &ContextSet {
+        &self.inner.default_contexts
+    }
+
+    /// The state of the circuit breaker, if the client has one.
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.inner.circuit_breaker.as_ref().map(|breaker| breaker.state())
+    }
 
-        let mut headers = HeaderMap::new();
-        headers.insert("X-API-KEY", api_key.parse().unwrap());
+    fn derive(&self, configure: impl FnOnce(&mut ClientInner)) -> LuckClient {
+        let mut inner = ClientInner::clone(&self.inner);
+        configure(&mut inner);
+        LuckClient { inner: Arc::new(inner) }
+    }
 
-        let client = reqwest::ClientBuilder::new()
-            .default_headers(headers).build()?;
+    /// Fill in the default contexts if a permission check doesn't specify any.
+    fn apply_default_contexts(&self, request: &mut PermissionCheckRequest) {
+        if request.query_options.contexts.is_empty() {
+            request.query_options.contexts = self.inner.default_contexts.clone();
+        }
+    }
+
+    /// Build the URL of an endpoint from its path segments, see [`endpoint_url`].
+    fn url(&self, segments: &[&str]) -> Result<Url, RequestError> {
+        endpoint_url(&self.inner.base_url, segments)
+    }
 
-        Ok(LuckClient {
-            base_url: url,
-            client
-        })
+    fn request(&self, method: Method, url: Url) -> HttpRequest {
+        request(&self.inner.api_key, method, url)
     }
-}
\ No newline at end of file
+
+    /// Send a request, retrying it according to the retry policy.
+    async fn dispatch(&self, request: HttpRequest, idempotent: bool) -> Result<HttpResponse, RequestError> {
+        let call = self.start_call(&request);
+        let mut attempts = 0;
+        let result = call.instrument(self.execute_attempts(request, idempotent, &mut attempts)).await;
+        self.finish_call(call, &result, attempts);
+        result
+    }
+
+    /// Send a request once, without checking its status, retrying or consulting the circuit breaker.
+    async fn send_once(&self, request: HttpRequest) -> Result<HttpResponse, RequestError> {
+        let call = self.start_call(&request);
+        let result = call.instrument(self.send_limited(request)).await;
+        self.finish_call(call, &result, 1);
+        result
+    }
+
+    /// Send a request until it succeeds or the retry policy gives up, counting the attempts made.
+    async fn execute_attempts(&self, request: HttpRequest, idempotent: bool, attempt: &mut u32) -> Result<HttpResponse, RequestError> {
+        loop {
+            *attempt += 1;
+            self.check_circuit().await?;
+
+            let result = match self.send_limited(request.clone()).await {
+                Ok(response) => check_status(response),
+                Err(e) => Err(e),
+            };
+
+            if let Some(breaker) = &self.inner.circuit_breaker {
+                breaker.record(&result);
+            }
+
+            let error = match result {
+                Ok(response) => return Ok(response),
+                Err(e) => e,
+            };
+
+            match self.inner.retry_policy.retry_after(*attempt, &error, idempotent) {
+                Some(delay) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(attempt = *attempt, error = %error, "retrying request");
+                    tokio::time::sleep(delay).await
+                },
+                None => return Err(error),
+            }
+        }
+    }
+
+    /// Send a request through the transport once the limiter allows it.
+    async fn send_limited(&self, request: HttpRequest) -> Result<HttpResponse, RequestError> {
+        let _permit = match &self.inner.limiter {
+            Some(limiter) => limiter.acquire().await,
+            None => None,
+        };
+        self.inner.transport.send(request).await
+    }
+
+    fn start_call(&self, request: &HttpRequest) -> Call {
+        let (endpoint, _target) = endpoint_route(&self.inner.base_url, &request.url);
+        Call {
+            #[cfg(feature = "tracing")]
+            trace: instrument::RequestTrace::start(request, &endpoint, _target.as_deref(), self.inner.log_payloads),
+            endpoint,
+            method: request.method.clone(),
+            request_bytes: request.body.as_ref().map_or(0, Vec::len),
+            started: Instant::now(),
+        }
+    }
+
+    fn finish_call(&self, call: Call, result: &Result<HttpResponse, RequestError>, attempts: u32) {
+        let duration = call.started.elapsed();
+        #[cfg(feature = "tracing")]
+        call.trace.finish(result, attempts, duration);
+
+        if let Some(recorder) = &self.inner.metrics_recorder {
+            recorder.record(&RequestMetrics {
+                endpoint: call.endpoint,
+                method: call.method,
+                status: match result {
+                    Ok(response) => Some(response.status),
+                    Err(e) => e.status(),
+                },
+                duration,
+                attempts,
+                request_bytes: call.request_bytes,
+                response_bytes: result.as_ref().map_or(0, |response| response.body.len()),
+            });
+        }
+    }
+
+    /// Fail fast if the circuit breaker is open, probing `/health` when it is due.
+    async fn check_circuit(&self) -> Result<(), RequestError> {
+        let Some(breaker) = &self.inner.circuit_breaker else {
+            return Ok(());
+        };
+
+        if let Permit::Probe(probe) = breaker.acquire()? {
+            let healthy = self.health().await.is_ok_and(|health| health.healthy);
+            probe.finish(healthy);
+            if !healthy {
+                return Err(RequestError::CircuitOpen);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Append path segments to the base URL.
+///
+/// Any path the base URL already has is kept, so instances behind a reverse proxy
+/// at e.g. `https://host/luckperms/` work. Each segment is percent-encoded, so names
+/// containing `/`, `?` or `#` stay a single segment.
+fn endpoint_url(base_url: &Url, segments: &[&str]) -> Result<Url, RequestError> {
+    let mut url = base_url.clone();
+    url.path_segments_mut()
+        .map_err(|_| url::ParseError::RelativeUrlWithCannotBeABaseBase)?
+        .pop_if_empty()
+        .extend(segments);
+    Ok(url)
+}
+
+/// Split a request URL into an endpoint template and its target, e.g.
+/// `/user/{uuid}/nodes` and the uuid, so spans of the same endpoint group together.
+fn endpoint_route(base_url: &Url, url: &Url) -> (String, Option<String>) {
+    let prefix = base_url.path_segments().map_or(0, |segments| segments.filter(|s| !s.is_empty()).count());
+    let segments: Vec<&str> = url.path_segments().map_or_else(Vec::new, |segments| segments.skip(prefix).collect());
+
+    let placeholder = match segments.as_slice() {
+        ["user", "lookup" | "search", ..] | ["group", "search", ..] => None,
+        ["user", _, ..] => Some((1, "{uuid}")),
+        ["group" | "track", _, ..] => Some((1, "{name}")),
+        ["messaging", "update", _, ..] => Some((2, "{uuid}")),
+        _ => None,
+    };
+
+    let mut endpoint = String::new();
+    let mut target = None;
+    for (i, segment) in segments.iter().enumerate() {
+        endpoint.push('/');
+        match placeholder {
+            Some((index, name)) if index == i => {
+                endpoint.push_str(name);
+                target = Some(segment.to_string());
+            },
+            _ => endpoint.push_str(segment),
+        }
+    }
+
+    (endpoint, target)
+}
+
+fn request(api_key: &HeaderValue, method: Method, url: Url) -> HttpRequest {
+    HttpRequest::new(method, url).header(API_KEY_HEADER, api_key.clone())
+}
+
+/// Book-keeping for a single API call, for tracing and metrics.
+struct Call {
+    #[cfg(feature = "tracing")]
+    trace: instrument::RequestTrace,
+    endpoint: String,
+    method: Method,
+    request_bytes: usize,
+    started: Instant,
+}
+
+impl Call {
+    #[cfg(feature = "tracing")]
+    fn instrument<F: Future>(&self, future: F) -> impl Future<Output = F::Output> {
+        tracing::Instrument::instrument(future, self.trace.span())
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    fn instrument<F: Future>(&self, future: F) -> impl Future<Output = F::Output> {
+        future
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(base: &str, segments: &[&str]) -> String {
+        endpoint_url(&Url::parse(base).unwrap(), segments).unwrap().to_string()
+    }
+
+    #[test]
+    fn client_is_shareable() {
+        fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+        assert_shareable::<LuckClient>();
+    }
+
+    #[test]
+    fn endpoint_url_without_prefix() {
+        assert_eq!(url("http://localhost:8080", &["user"]), "http://localhost:8080/user");
+        assert_eq!(url("http://localhost:8080/", &["group", "admin", "nodes"]), "http://localhost:8080/group/admin/nodes");
+    }
+
+    #[test]
+    fn endpoint_url_keeps_base_path() {
+        assert_eq!(url("https://host/luckperms/", &["user"]), "https://host/luckperms/user");
+        assert_eq!(url("https://host/luckperms", &["track", "staff"]), "https://host/luckperms/track/staff");
+        assert_eq!(url("https://host/a/b/", &["event", "log-broadcast"]), "https://host/a/b/event/log-broadcast");
+    }
+
+    #[test]
+    fn endpoint_url_encodes_segments() {
+        assert_eq!(url("https://host/luckperms/", &["group", "a/b?c#d"]), "https://host/luckperms/group/a%2Fb%3Fc%23d");
+        assert_eq!(url("http://localhost", &["group", "with space"]), "http://localhost/group/with%20space");
+    }
+
+    fn route(base: &str, url: &str) -> (String, Option<String>) {
+        endpoint_route(&Url::parse(base).unwrap(), &Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn endpoint_route_replaces_targets() {
+        let uuid = "069a79f4-44e9-4726-a5be-fca90e38aaf5";
+        assert_eq!(
+            route("http://localhost:8080", &format!("http://localhost:8080/user/{uuid}/nodes")),
+            ("/user/{uuid}/nodes".to_string(), Some(uuid.to_string())),
+        );
+        assert_eq!(
+            route("https://host/luckperms/", "https://host/luckperms/group/admin?x=1"),
+            ("/group/{name}".to_string(), Some("admin".to_string())),
+        );
+    }
+
+    #[test]
+    fn endpoint_route_keeps_fixed_segments() {
+        assert_eq!(route("http://localhost", "http://localhost/user/search?key=a"), ("/user/search".to_string(), None));
+        assert_eq!(route("http://localhost", "http://localhost/messaging/update"), ("/messaging/update".to_string(), None));
+        assert_eq!(route("http://localhost", "http://localhost/health"), ("/health".to_string(), None));
+    }
+}
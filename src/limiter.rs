@@ -0,0 +1,104 @@
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tokio::time::Instant;
+
+/// Limits how many requests a [`crate::LuckClient`] sends, to avoid overwhelming
+/// the LuckPerms instance and its database.
+///
+/// Every attempt of a request waits for both a free in-flight slot and a token from
+/// the rate limit. Waiting is cancellation safe: dropping a pending call gives up
+/// its place without consuming a slot or token. Event streams are not limited.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use luckperms_rs::limiter::Limiter;
+/// use luckperms_rs::LuckClient;
+///
+/// fn main() {
+///     let client = LuckClient::builder("http://localhost:8080".to_string(), "YOUR API KEY".to_string())
+///         .limiter(Limiter::new().max_in_flight(8).rate_limit(50, Duration::from_secs(1)))
+///         .build()
+///         .unwrap();
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct Limiter {
+    in_flight: Option<Semaphore>,
+    bucket: Option<Mutex<TokenBucket>>,
+}
+
+impl Limiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow at most `max` requests to be in flight at once. A `max` of 0 is treated as 1, and
+    /// values above [`Semaphore::MAX_PERMITS`] as that maximum.
+    pub fn max_in_flight(mut self, max: usize) -> Self {
+        self.in_flight = Some(Semaphore::new(max.clamp(1, Semaphore::MAX_PERMITS)));
+        self
+    }
+
+    /// Allow at most `requests` requests per `period`, with bursts of up to `requests`.
+    /// A `requests` of 0 is treated as 1.
+    pub fn rate_limit(mut self, requests: u32, period: Duration) -> Self {
+        self.bucket = Some(Mutex::new(TokenBucket::new(requests, period)));
+        self
+    }
+
+    /// Wait until a request may be sent. The returned permit holds an in-flight slot until dropped.
+    pub(crate) async fn acquire(&self) -> Option<SemaphorePermit<'_>> {
+        let permit = match &self.in_flight {
+            Some(semaphore) => Some(semaphore.acquire().await.expect("limiter semaphore is never closed")),
+            None => None,
+        };
+
+        if let Some(bucket) = &self.bucket {
+            loop {
+                let wait = bucket.lock().unwrap().take();
+                match wait {
+                    Some(wait) => tokio::time::sleep(wait).await,
+                    None => break,
+                }
+            }
+        }
+
+        permit
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    per_token: Duration,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(requests: u32, period: Duration) -> Self {
+        let capacity = f64::from(requests.max(1));
+        Self {
+            capacity,
+            tokens: capacity,
+            per_token: period.div_f64(capacity),
+            refilled_at: Instant::now(),
+        }
+    }
+
+    /// Take a token, or return how long to wait until one is available.
+    fn take(&mut self) -> Option<Duration> {
+        let now = Instant::now();
+        let refilled = now.duration_since(self.refilled_at).as_secs_f64() / self.per_token.as_secs_f64();
+        self.tokens = (self.tokens + refilled).min(self.capacity);
+        self.refilled_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(self.per_token.mul_f64(1.0 - self.tokens))
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use reqwest::Method;
+use serde::de::IgnoredAny;
+use serde::Serialize;
+use uuid::Uuid;
+use crate::LuckClient;
+use crate::endpoint::{json_body, Endpoint};
+use crate::errors::RequestError;
+use crate::models::CustomMessage;
+
+/// `POST /messaging/update` or `/messaging/update/{uuid}`, push an update to the network.
+#[derive(Debug, Clone)]
+pub struct PushUpdate {
+    /// The user to push an update for, or `None` for a full update.
+    pub uuid: Option<Uuid>,
+}
+
+impl Endpoint for PushUpdate {
+    type Response = IgnoredAny;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path(&self) -> Vec<String> {
+        let mut path = vec!["messaging".to_string(), "update".to_string()];
+        path.extend(self.uuid.map(|uuid| uuid.to_string()));
+        path
+    }
+}
+
+/// `POST /messaging/custom`, send a custom message.
+#[derive(Debug, Clone)]
+pub struct SendCustomMessage {
+    pub message: CustomMessage,
+}
+
+impl Endpoint for SendCustomMessage {
+    type Response = IgnoredAny;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["messaging".to_string(), "custom".to_string()]
+    }
+
+    fn body(&self) -> Result<Option<Vec<u8>>, RequestError> {
+        json_body(&self.message)
+    }
+}
+
+impl LuckClient {
+    /// Push a full update to the other servers on the network, like `/lp sync`.
+    pub async fn push_network_update(&self) -> Result<(), RequestError> {
+        self.execute(PushUpdate { uuid: None }).await?;
+        Ok(())
+    }
+
+    /// Push an update for a single user to the other servers on the network.
+    pub async fn push_user_update(&self, uuid: Uuid) -> Result<(), RequestError> {
+        self.execute(PushUpdate { uuid: Some(uuid) }).await?;
+        Ok(())
+    }
+
+    /// Send a custom message on a channel, encoding the payload as JSON.
+    ///
+    /// The payload is always JSON-encoded, so a string is sent quoted: `&"hello"` arrives as `"\"hello\""`.
+    /// Receivers can decode it again with [`CustomMessage::payload_json`].
+    pub async fn send_custom_message<T: Serialize>(&self, channel_id: String, payload: &T) -> Result<(), RequestError> {
+        let message = CustomMessage {
+            channel_id,
+            payload: serde_json::to_string(payload)?,
+        };
+        self.execute(SendCustomMessage { message }).await?;
+        Ok(())
+    }
+}
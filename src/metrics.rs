@@ -0,0 +1,69 @@
+use std::fmt::Debug;
+use std::time::Duration;
+use reqwest::{Method, StatusCode};
+
+/// Measurements of a single API call, including any retries.
+#[derive(Debug, Clone)]
+pub struct RequestMetrics {
+    /// The endpoint template, e.g. `/user/{uuid}/permissionCheck`.
+    pub endpoint: String,
+    pub method: Method,
+    /// The final response status, `None` if no response was received.
+    pub status: Option<StatusCode>,
+    pub duration: Duration,
+    pub attempts: u32,
+    pub request_bytes: usize,
+    pub response_bytes: usize,
+}
+
+impl RequestMetrics {
+    /// The status class, e.g. `2xx`, or `error` if no response was received.
+    pub fn status_class(&self) -> &'static str {
+        match self.status.map(|status| status.as_u16() / 100) {
+            Some(1) => "1xx",
+            Some(2) => "2xx",
+            Some(3) => "3xx",
+            Some(4) => "4xx",
+            Some(5) => "5xx",
+            _ => "error",
+        }
+    }
+}
+
+/// Receives the metrics of every call a [`crate::LuckClient`] makes.
+pub trait MetricsRecorder: Debug + Send + Sync {
+    fn record(&self, metrics: &RequestMetrics);
+}
+
+/// A [`MetricsRecorder`] that reports to the [`metrics`](::metrics) crate facade.
+///
+/// Records `luckperms_requests_total`, `luckperms_request_duration_seconds`,
+/// `luckperms_request_retries_total`, `luckperms_request_bytes_total` and
+/// `luckperms_response_bytes_total`, labeled by endpoint and method.
+#[cfg(feature = "metrics")]
+#[derive(Debug, Clone, Default)]
+pub struct MetricsFacadeRecorder;
+
+#[cfg(feature = "metrics")]
+impl MetricsRecorder for MetricsFacadeRecorder {
+    fn record(&self, metrics: &RequestMetrics) {
+        let endpoint = metrics.endpoint.clone();
+        let method = metrics.method.to_string();
+
+        ::metrics::counter!("luckperms_requests_total",
+            "endpoint" => endpoint.clone(), "method" => method.clone(), "status" => metrics.status_class())
+            .increment(1);
+        ::metrics::histogram!("luckperms_request_duration_seconds",
+            "endpoint" => endpoint.clone(), "method" => method.clone())
+            .record(metrics.duration.as_secs_f64());
+        ::metrics::counter!("luckperms_request_retries_total",
+            "endpoint" => endpoint.clone(), "method" => method.clone())
+            .increment(u64::from(metrics.attempts.saturating_sub(1)));
+        ::metrics::counter!("luckperms_request_bytes_total",
+            "endpoint" => endpoint.clone(), "method" => method.clone())
+            .increment(metrics.request_bytes as u64);
+        ::metrics::counter!("luckperms_response_bytes_total",
+            "endpoint" => endpoint, "method" => method)
+            .increment(metrics.response_bytes as u64);
+    }
+}
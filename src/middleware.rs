@@ -0,0 +1,88 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use futures_util::future::BoxFuture;
+use crate::errors::RequestError;
+use crate::transport::{HttpRequest, HttpResponse, StreamingResponse, Transport};
+
+/// Hooks that run around every HTTP request a [`crate::LuckClient`] sends, e.g. to add
+/// correlation ids, sign requests or log slow calls.
+///
+/// Middleware runs once per attempt, so a retried request passes through it again.
+/// `before_request` hooks run in the order the middleware was added, `after_response`
+/// and `on_error` hooks in reverse order.
+pub trait Middleware: Debug + Send + Sync {
+    /// Inspect or modify a request before it is sent.
+    fn before_request(&self, request: &mut HttpRequest) -> Result<(), RequestError> {
+        let _ = request;
+        Ok(())
+    }
+
+    /// Inspect or modify a response before its status is checked.
+    ///
+    /// This is not called for event streams, whose body is still being received.
+    fn after_response(&self, request: &HttpRequest, response: &mut HttpResponse, elapsed: Duration) -> Result<(), RequestError> {
+        let _ = (request, response, elapsed);
+        Ok(())
+    }
+
+    /// Observe a request that failed without a response, e.g. because the connection
+    /// was reset or timed out.
+    fn on_error(&self, request: &HttpRequest, error: &RequestError, elapsed: Duration) {
+        let _ = (request, error, elapsed);
+    }
+}
+
+/// A [`Transport`] that runs a chain of [`Middleware`] around another transport.
+#[derive(Debug)]
+pub(crate) struct MiddlewareTransport {
+    inner: Arc<dyn Transport>,
+    middleware: Vec<Arc<dyn Middleware>>,
+}
+
+impl MiddlewareTransport {
+    pub(crate) fn new(inner: Arc<dyn Transport>, middleware: Vec<Arc<dyn Middleware>>) -> Self {
+        Self { inner, middleware }
+    }
+
+    fn before_request(&self, request: &mut HttpRequest) -> Result<(), RequestError> {
+        for middleware in &self.middleware {
+            middleware.before_request(request)?;
+        }
+        Ok(())
+    }
+}
+
+impl Transport for MiddlewareTransport {
+    fn send(&self, mut request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, RequestError>> {
+        Box::pin(async move {
+            self.before_request(&mut request)?;
+
+            let started = Instant::now();
+            let result = self.inner.send(request.clone()).await;
+            let elapsed = started.elapsed();
+
+            let mut response = match result {
+                Ok(response) => response,
+                Err(error) => {
+                    for middleware in self.middleware.iter().rev() {
+                        middleware.on_error(&request, &error, elapsed);
+                    }
+                    return Err(error);
+                },
+            };
+
+            for middleware in self.middleware.iter().rev() {
+                middleware.after_response(&request, &mut response, elapsed)?;
+            }
+            Ok(response)
+        })
+    }
+
+    fn stream(&self, mut request: HttpRequest) -> BoxFuture<'_, Result<StreamingResponse, RequestError>> {
+        Box::pin(async move {
+            self.before_request(&mut request)?;
+            self.inner.stream(request).await
+        })
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::BTreeSet;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Context {
+    pub key: String,
+    pub value: String,
+}
+
+impl Context {
+    pub fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// A set of contexts, ordered by key and then value.
+///
+/// A key may be present with multiple values, e.g. `world=nether` and `world=the_end`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ContextSet {
+    contexts: BTreeSet<Context>,
+}
+
+impl ContextSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a key-value pair, returning `false` if it was already present.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) -> bool {
+        self.contexts.insert(Context::new(key, value))
+    }
+
+    /// Remove a key-value pair, returning `true` if it was present.
+    pub fn remove(&mut self, key: &str, value: &str) -> bool {
+        self.contexts.remove(&Context::new(key, value))
+    }
+
+    /// Remove every value for a key.
+    pub fn remove_key(&mut self, key: &str) {
+        self.contexts.retain(|context| context.key != key);
+    }
+
+    pub fn contains(&self, key: &str, value: &str) -> bool {
+        self.contexts.contains(&Context::new(key, value))
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).next().is_some()
+    }
+
+    /// Get all values for a key, in order.
+    pub fn get<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.contexts.iter()
+            .filter(move |context| context.key == key)
+            .map(|context| context.value.as_str())
+    }
+
+    /// Check whether every context in `self` is also in `other`.
+    pub fn is_satisfied_by(&self, other: &ContextSet) -> bool {
+        self.contexts.is_subset(&other.contexts)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Context> {
+        self.contexts.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.contexts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contexts.is_empty()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for ContextSet {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self {
+            contexts: iter.into_iter().map(|(key, value)| Context::new(key, value)).collect(),
+        }
+    }
+}
+
+impl FromIterator<Context> for ContextSet {
+    fn from_iter<T: IntoIterator<Item = Context>>(iter: T) -> Self {
+        Self {
+            contexts: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for ContextSet {
+    type Item = Context;
+    type IntoIter = std::collections::btree_set::IntoIter<Context>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.contexts.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ContextSet {
+    type Item = &'a Context;
+    type IntoIter = std::collections::btree_set::Iter<'a, Context>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.contexts.iter()
+    }
+}
+
+impl Serialize for ContextSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(&self.contexts)
+    }
+}
+
+impl<'de> Deserialize<'de> for ContextSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vec::<Context>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
\ No newline at end of file
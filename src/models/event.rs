@@ -0,0 +1,62 @@
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+use crate::models::Action;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogBroadcast {
+    pub entry: Action,
+    pub origin: LogBroadcastOrigin,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LogBroadcastOrigin {
+    Local,
+    LocalApi,
+    Remote,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreNetworkSync {
+    pub sync_id: Uuid,
+    #[serde(rename = "type")]
+    pub type_: SyncType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub specific_user_unique_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostNetworkSync {
+    pub sync_id: Uuid,
+    #[serde(rename = "type")]
+    pub type_: SyncType,
+    pub did_sync_occur: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub specific_user_unique_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SyncType {
+    Full,
+    SpecificUser,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomMessage {
+    pub channel_id: String,
+    pub payload: String,
+}
+
+impl CustomMessage {
+    /// Decode the payload of this message as JSON.
+    pub fn payload_json<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_str(&self.payload)
+    }
+}
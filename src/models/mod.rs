@@ -2,8 +2,18 @@ mod user;
 mod node;
 mod group;
 mod action;
+mod track;
+mod event;
+mod health;
+mod context;
+mod tristate;
 
 pub use user::*;
 pub use node::*;
 pub use group::*;
-pub use action::*;
\ No newline at end of file
+pub use action::*;
+pub use track::*;
+pub use event::*;
+pub use health::*;
+pub use context::*;
+pub use tristate::*;
\ No newline at end of file
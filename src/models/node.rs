@@ -1,16 +1,20 @@
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::models::ContextSet;
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct Node {
     pub key: String,
     #[serde(rename = "type")]
     pub type_: NodeType,
     pub value: bool,
-    pub context: Vec<String>,
+    #[serde(default)]
+    pub context: ContextSet,
     pub expiry: Option<u64>,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum NodeType {
+    Permission,
     RegexPermission,
     Inheritance,
     Prefix,
@@ -18,18 +22,76 @@ pub enum NodeType {
     Meta,
     Weight,
     DisplayName,
+    /// A node type this crate doesn't know about, e.g. one added by a newer server.
+    Unknown(String),
+}
+
+impl std::fmt::Display for NodeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Permission => "permission",
+            Self::RegexPermission => "regex_permission",
+            Self::Inheritance => "inheritance",
+            Self::Prefix => "prefix",
+            Self::Suffix => "suffix",
+            Self::Meta => "meta",
+            Self::Weight => "weight",
+            Self::DisplayName => "display_name",
+            Self::Unknown(type_) => type_,
+        })
+    }
+}
+
+impl std::str::FromStr for NodeType {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "permission" => Self::Permission,
+            "regex_permission" => Self::RegexPermission,
+            "inheritance" => Self::Inheritance,
+            "prefix" => Self::Prefix,
+            "suffix" => Self::Suffix,
+            "meta" => Self::Meta,
+            "weight" => Self::Weight,
+            "display_name" => Self::DisplayName,
+            other => Self::Unknown(other.to_string()),
+        })
+    }
+}
+
+impl Serialize for NodeType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for NodeType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let type_ = String::deserialize(deserializer)?;
+        Ok(type_.parse().unwrap_or_else(|never| match never {}))
+    }
+}
+
+/// How temporary nodes being added should be merged with existing temporary nodes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemporaryMergeStrategy {
+    /// Add the duration of the new node to the existing node.
+    AddNewDurationToExisting,
+    /// Replace the existing node if the new node expires later.
+    ReplaceExistingIfDurationLonger,
+    /// Add the node as is.
+    #[default]
+    None,
 }
 
-impl NodeType {
-    pub fn to_string(&self) -> String {
-        match self {
-            Self::RegexPermission => "regex_permission".to_string(),
-            Self::Inheritance => "inheritance".to_string(),
-            Self::Prefix => "prefix".to_string(),
-            Self::Suffix => "suffix".to_string(),
-            Self::Meta => "meta".to_string(),
-            Self::Weight => "weight".to_string(),
-            Self::DisplayName => "display_name".to_string(),
-        }
+impl std::fmt::Display for TemporaryMergeStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::AddNewDurationToExisting => "add_new_duration_to_existing",
+            Self::ReplaceExistingIfDurationLonger => "replace_existing_if_duration_longer",
+            Self::None => "none",
+        })
     }
 }
\ No newline at end of file
@@ -0,0 +1,42 @@
+/// The result of a permission check.
+///
+/// `Undefined` means no node matched, so the platform default applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tristate {
+    True,
+    False,
+    Undefined,
+}
+
+impl Tristate {
+    /// Get the explicit value, if any.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::True => Some(true),
+            Self::False => Some(false),
+            Self::Undefined => None,
+        }
+    }
+
+    /// Get the explicit value, falling back to `default` when undefined.
+    pub fn as_bool_or(&self, default: bool) -> bool {
+        self.as_bool().unwrap_or(default)
+    }
+
+    pub fn is_defined(&self) -> bool {
+        *self != Self::Undefined
+    }
+}
+
+impl From<bool> for Tristate {
+    fn from(value: bool) -> Self {
+        if value { Self::True } else { Self::False }
+    }
+}
+
+impl From<Option<bool>> for Tristate {
+    fn from(value: Option<bool>) -> Self {
+        value.map_or(Self::Undefined, Self::from)
+    }
+}
\ No newline at end of file
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use uuid::Uuid;
 use crate::models::node::Node;
+use crate::models::Tristate;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -51,7 +52,7 @@ pub struct UsernameUpdateRequest {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PermissionCheckResult {
-    pub result: bool,
+    pub result: Tristate,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub node: Option<Node>,
@@ -68,6 +69,14 @@ pub struct TrackMoveRequest {
 pub struct TrackMoveResponse {
     pub success: bool,
     pub status: String,
-    pub group_from: String,
-    pub group_to: String,
+    /// The group the user was moved from. LuckPerms omits it when the user was
+    /// added to the first group of the track (`added_to_first`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub group_from: Option<String>,
+    /// The group the user was moved to. LuckPerms omits it when the user was
+    /// removed from the track entirely (`removed_from_first`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub group_to: Option<String>,
 }
\ No newline at end of file
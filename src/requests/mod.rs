@@ -2,4 +2,7 @@ mod user_search;
 mod permission_check;
 
 pub use user_search::*;
-pub use permission_check::*;
\ No newline at end of file
+pub use permission_check::*;
+
+/// Moved to [`crate::models`], re-exported here for compatibility.
+pub use crate::models::Context;
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use crate::models::ContextSet;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -7,15 +8,28 @@ pub struct PermissionCheckRequest {
     pub query_options: QueryOptions,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl PermissionCheckRequest {
+    /// Check a permission with the default query options.
+    pub fn new(permission: impl Into<String>) -> Self {
+        Self {
+            permission: permission.into(),
+            query_options: QueryOptions::default(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mode: Option<QueryMode>,
+    /// Omitted from the JSON when empty, so it may also be missing when read back.
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
     pub flags: Vec<QueryFlag>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub contexts: Vec<Context>,
+    #[serde(skip_serializing_if = "ContextSet::is_empty")]
+    #[serde(default)]
+    pub contexts: ContextSet,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -33,11 +47,4 @@ pub enum QueryFlag {
     IncludeNodesWithoutWorldContext,
     ApplyInheritanceNodesWithoutServerContext,
     ApplyInheritanceNodesWithoutWorldContext,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Context {
-    pub key: String,
-    pub value: String,
 }
\ No newline at end of file
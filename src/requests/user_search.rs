@@ -1,6 +1,7 @@
 use url::UrlQuery;
 use crate::models::NodeType;
 
+#[derive(Debug, Clone)]
 pub enum SearchRequest {
     Key {
         key: String,
@@ -60,25 +61,20 @@ impl SearchRequest {
     }
 
     pub fn set_query_values(&self, mut query: form_urlencoded::Serializer<'_, UrlQuery<'_>>) {
-        match self {
-            Self::Key { key, type_ } => {
-                query.append_pair("key", key);
-                if let Some(type_) = type_ {
-                    query.append_pair("type", &type_.to_string());
-                }
-            },
-            Self::KeyStartsWith { key_starts_with, type_ } => {
-                query.append_pair("keyStartsWith", key_starts_with);
-                if let Some(type_) = type_ {
-                    query.append_pair("type", &type_.to_string());
-                }
-            },
-            Self::MetaKey { meta_key, type_ } => {
-                query.append_pair("metaKey", meta_key);
-                if let Some(type_) = type_ {
-                    query.append_pair("type", &type_.to_string());
-                }
-            },
+        query.extend_pairs(self.query_pairs());
+    }
+
+    pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let (pair, type_) = match self {
+            Self::Key { key, type_ } => (("key", key.clone()), type_),
+            Self::KeyStartsWith { key_starts_with, type_ } => (("keyStartsWith", key_starts_with.clone()), type_),
+            Self::MetaKey { meta_key, type_ } => (("metaKey", meta_key.clone()), type_),
+        };
+
+        let mut pairs = vec![pair];
+        if let Some(type_) = type_ {
+            pairs.push(("type", type_.to_string()));
         }
+        pairs
     }
 }
\ No newline at end of file
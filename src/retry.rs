@@ -0,0 +1,71 @@
+use std::fmt::Debug;
+use std::time::Duration;
+use rand::Rng;
+use reqwest::StatusCode;
+use crate::errors::RequestError;
+
+/// Decides whether a failed request should be sent again.
+pub trait RetryPolicy: Debug + Send + Sync {
+    /// Get the delay before retry number `attempt` (starting at 1), or `None` to give up.
+    ///
+    /// `idempotent` is `false` for requests that may have side effects when repeated,
+    /// such as adding nodes or promoting a user.
+    fn retry_after(&self, attempt: u32, error: &RequestError, idempotent: bool) -> Option<Duration>;
+}
+
+/// Never retry. This is the default policy.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoRetry;
+
+impl RetryPolicy for NoRetry {
+    fn retry_after(&self, _attempt: u32, _error: &RequestError, _idempotent: bool) -> Option<Duration> {
+        None
+    }
+}
+
+/// Retry transient failures with jittered exponential backoff.
+#[derive(Debug, Clone)]
+pub struct ExponentialBackoff {
+    pub max_retries: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    /// Also retry requests that are not idempotent, e.g. `add_user_node` or `promote_user`.
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for ExponentialBackoff {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(10),
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy for ExponentialBackoff {
+    fn retry_after(&self, attempt: u32, error: &RequestError, idempotent: bool) -> Option<Duration> {
+        if attempt > self.max_retries || (!idempotent && !self.retry_non_idempotent) || !is_transient(error) {
+            return None;
+        }
+
+        let factor = 2u32.saturating_pow(attempt - 1);
+        let delay = self.initial_delay.saturating_mul(factor).min(self.max_delay);
+        // Full jitter, so that many clients recovering at once don't retry in lockstep.
+        Some(delay.mul_f64(rand::thread_rng().gen_range(0.0..=1.0)))
+    }
+}
+
+/// Check whether an error is likely to go away when the request is retried.
+pub fn is_transient(error: &RequestError) -> bool {
+    match error {
+        RequestError::Http(e) => e.is_connect() || e.is_timeout() || e.is_request(),
+        RequestError::Server { status, .. } => matches!(
+            *status,
+            StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+        ),
+        RequestError::Status { status, .. } => *status == StatusCode::TOO_MANY_REQUESTS,
+        _ => false,
+    }
+}
\ No newline at end of file
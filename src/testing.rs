@@ -0,0 +1,686 @@
+//! An in-memory fake of the LuckPerms REST API, for testing code that uses [`LuckClient`].
+//!
+//! ```rust
+//! use luckperms_rs::testing::FakeLuckPerms;
+//!
+//! # async fn run() {
+//! let fake = FakeLuckPerms::new();
+//! let client = fake.client();
+//! let groups = client.get_groups().await.unwrap();
+//! assert_eq!(groups, vec!["default".to_string()]);
+//! # }
+//! ```
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use futures_util::future::BoxFuture;
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use uuid::Uuid;
+use crate::errors::RequestError;
+use crate::LuckClient;
+use crate::models::{Action, ContextSet, CustomMessage, Group, GroupCreateRequest, GroupSearchResult, Health, HealthDetails, Metadata, Node, NodeType, PermissionCheckResult, TemporaryMergeStrategy, Track, TrackCreateRequest, TrackMoveRequest, TrackMoveResponse, TrackUpdateRequest, Tristate, User, UserIdentifier, UsernameUpdateRequest, UserSearchResult};
+use crate::requests::PermissionCheckRequest;
+use crate::transport::{HttpRequest, HttpResponse, Transport};
+
+const DEFAULT_GROUP: &str = "default";
+const ROOTS: [&str; 7] = ["user", "group", "track", "action", "health", "messaging", "event"];
+
+/// An in-memory LuckPerms instance that can be used as a [`Transport`].
+///
+/// Clones share the same state, so a clone can be kept around to inspect or
+/// seed the state while a [`LuckClient`] uses another.
+#[derive(Debug, Clone)]
+pub struct FakeLuckPerms {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    users: BTreeMap<Uuid, FakeUser>,
+    groups: BTreeMap<String, Vec<Node>>,
+    tracks: BTreeMap<String, Vec<String>>,
+    actions: Vec<Action>,
+    custom_messages: Vec<CustomMessage>,
+    network_updates: usize,
+}
+
+#[derive(Debug, Clone)]
+struct FakeUser {
+    username: String,
+    nodes: Vec<Node>,
+}
+
+type Reply = Result<HttpResponse, FakeError>;
+
+/// An error response, sent as `{"message": ...}` like LuckPerms does.
+#[derive(Debug)]
+struct FakeError {
+    status: StatusCode,
+    message: String,
+}
+
+impl Default for FakeLuckPerms {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeLuckPerms {
+    /// Create a fake instance containing only the `default` group, like a fresh LuckPerms install.
+    pub fn new() -> Self {
+        let mut state = State::default();
+        state.groups.insert(DEFAULT_GROUP.to_string(), Vec::new());
+
+        Self {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Create a [`LuckClient`] that sends its requests to this fake.
+    pub fn client(&self) -> LuckClient {
+        LuckClient::builder("http://luckperms.test/".to_string(), "fake".to_string())
+            .transport(self.clone())
+            .build()
+            .expect("the fake base URL and API key are valid")
+    }
+
+    /// Add a user with the `default` group, replacing any existing user with the same UUID.
+    pub fn insert_user(&self, unique_id: Uuid, username: impl Into<String>) {
+        self.state().users.insert(unique_id, FakeUser::new(username.into()));
+    }
+
+    /// Add a group with the given nodes, replacing any existing group with the same name.
+    pub fn insert_group(&self, name: impl Into<String>, nodes: Vec<Node>) {
+        self.state().groups.insert(name.into(), nodes);
+    }
+
+    /// Add a track, replacing any existing track with the same name.
+    pub fn insert_track(&self, name: impl Into<String>, groups: Vec<String>) {
+        self.state().tracks.insert(name.into(), groups);
+    }
+
+    /// All actions submitted so far.
+    pub fn actions(&self) -> Vec<Action> {
+        self.state().actions.clone()
+    }
+
+    /// All custom messages sent so far.
+    pub fn custom_messages(&self) -> Vec<CustomMessage> {
+        self.state().custom_messages.clone()
+    }
+
+    /// How many network or user updates were pushed through `/messaging/update`.
+    pub fn network_updates(&self) -> usize {
+        self.state().network_updates
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn handle(&self, request: &HttpRequest) -> HttpResponse {
+        let segments: Vec<String> = request.url.path_segments()
+            .map(|segments| segments.map(percent_decode).collect())
+            .unwrap_or_default();
+        let start = segments.iter().position(|segment| ROOTS.contains(&segment.as_str()));
+        let path: Vec<&str> = match start {
+            Some(start) => segments[start..].iter().map(String::as_str).filter(|s| !s.is_empty()).collect(),
+            None => return error_response(not_found("unknown endpoint")),
+        };
+        let query: HashMap<String, String> = request.url.query_pairs().into_owned().collect();
+
+        let mut state = self.state();
+        let reply = match path[0] {
+            "user" => state.handle_user(&request.method, &path[1..], &query, request),
+            "group" => state.handle_group(&request.method, &path[1..], &query, request),
+            "track" => state.handle_track(&request.method, &path[1..], request),
+            "action" => state.handle_action(&request.method, request),
+            "health" => Ok(json(StatusCode::OK, &Health {
+                healthy: true,
+                details: HealthDetails {
+                    storage_connected: Some(true),
+                    storage_ping: Some(0),
+                    ..HealthDetails::default()
+                },
+            })),
+            "messaging" => state.handle_messaging(&request.method, &path[1..], request),
+            // Event streams end immediately, nothing is ever pushed.
+            "event" if request.method == Method::GET => Ok(empty(StatusCode::OK)),
+            _ => Err(not_found("unknown endpoint")),
+        };
+
+        reply.unwrap_or_else(error_response)
+    }
+}
+
+impl Transport for FakeLuckPerms {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, RequestError>> {
+        Box::pin(async move { Ok(self.handle(&request)) })
+    }
+}
+
+impl FakeUser {
+    fn new(username: String) -> Self {
+        Self {
+            username,
+            nodes: vec![inheritance(DEFAULT_GROUP)],
+        }
+    }
+}
+
+impl State {
+    fn handle_user(&mut self, method: &Method, path: &[&str], query: &HashMap<String, String>, request: &HttpRequest) -> Reply {
+        match (method, path) {
+            (&Method::GET, []) => Ok(json(StatusCode::OK, &self.users.keys().collect::<Vec<_>>())),
+            (&Method::POST, []) => {
+                let identifier: UserIdentifier = body(request)?;
+                if self.users.contains_key(&identifier.unique_id) {
+                    return Err(error(StatusCode::CONFLICT, "user already exists"));
+                }
+                self.users.insert(identifier.unique_id, FakeUser::new(identifier.username));
+                Ok(json(StatusCode::CREATED, &self.user(identifier.unique_id)?))
+            },
+            (&Method::GET, ["lookup"]) => {
+                let found = if let Some(username) = query.get("username") {
+                    self.users.iter().find(|(_, user)| user.username.eq_ignore_ascii_case(username))
+                } else if let Some(unique_id) = query.get("uniqueId") {
+                    let unique_id = parse_uuid(unique_id)?;
+                    self.users.iter().find(|(id, _)| **id == unique_id)
+                } else {
+                    return Err(error(StatusCode::BAD_REQUEST, "missing username or uniqueId"));
+                };
+                let (unique_id, user) = found.ok_or_else(|| not_found("user not found"))?;
+                Ok(json(StatusCode::OK, &UserIdentifier {
+                    unique_id: *unique_id,
+                    username: user.username.clone(),
+                }))
+            },
+            (&Method::GET, ["search"]) => {
+                let results: Vec<UserSearchResult> = self.users.iter()
+                    .map(|(unique_id, user)| UserSearchResult {
+                        unique_id: *unique_id,
+                        results: search(&user.nodes, query),
+                    })
+                    .filter(|result| !result.results.is_empty())
+                    .collect();
+                Ok(json(StatusCode::OK, &results))
+            },
+            (_, [unique_id, rest @ ..]) => {
+                let unique_id = parse_uuid(unique_id)?;
+                if !self.users.contains_key(&unique_id) {
+                    return Err(not_found("user not found"));
+                }
+                self.handle_existing_user(method, unique_id, rest, query, request)
+            },
+            _ => Err(not_found("unknown endpoint")),
+        }
+    }
+
+    fn handle_existing_user(&mut self, method: &Method, unique_id: Uuid, path: &[&str], query: &HashMap<String, String>, request: &HttpRequest) -> Reply {
+        match (method, path) {
+            (&Method::GET, []) => Ok(json(StatusCode::OK, &self.user(unique_id)?)),
+            (&Method::PATCH, []) => {
+                let update: UsernameUpdateRequest = body(request)?;
+                self.user_mut(unique_id)?.username = update.username;
+                Ok(empty(StatusCode::OK))
+            },
+            (&Method::DELETE, []) => {
+                self.users.remove(&unique_id);
+                Ok(empty(StatusCode::OK))
+            },
+            (_, ["nodes"]) => {
+                let nodes = &mut self.user_mut(unique_id)?.nodes;
+                let nodes = handle_nodes(method, nodes, query, request)?;
+                Ok(json(StatusCode::OK, &nodes))
+            },
+            (&Method::GET, ["meta"]) => {
+                let nodes = self.resolve_nodes(&self.users[&unique_id].nodes);
+                Ok(json(StatusCode::OK, &metadata(&nodes)))
+            },
+            (_, ["permissionCheck"]) => {
+                self.permission_check(method, &self.users[&unique_id].nodes, query, request)
+            },
+            (&Method::POST, [direction @ ("promote" | "demote")]) => {
+                let move_request: TrackMoveRequest = body(request)?;
+                let track = self.tracks.get(&move_request.track).cloned()
+                    .ok_or_else(|| not_found("track not found"))?;
+                let user = self.user_mut(unique_id)?;
+                let response = move_along_track(&mut user.nodes, &track, *direction == "promote");
+                Ok(json(StatusCode::OK, &response))
+            },
+            _ => Err(not_found("unknown endpoint")),
+        }
+    }
+
+    fn handle_group(&mut self, method: &Method, path: &[&str], query: &HashMap<String, String>, request: &HttpRequest) -> Reply {
+        match (method, path) {
+            (&Method::GET, []) => Ok(json(StatusCode::OK, &self.groups.keys().collect::<Vec<_>>())),
+            (&Method::POST, []) => {
+                let create: GroupCreateRequest = body(request)?;
+                if self.groups.contains_key(&create.name) {
+                    return Err(error(StatusCode::CONFLICT, "group already exists"));
+                }
+                self.groups.insert(create.name.clone(), Vec::new());
+                Ok(json(StatusCode::CREATED, &self.group(&create.name)?))
+            },
+            (&Method::GET, ["search"]) => {
+                let results: Vec<GroupSearchResult> = self.groups.iter()
+                    .map(|(name, nodes)| GroupSearchResult {
+                        name: name.clone(),
+                        results: search(nodes, query),
+                    })
+                    .filter(|result| !result.results.is_empty())
+                    .collect();
+                Ok(json(StatusCode::OK, &results))
+            },
+            (_, [name, rest @ ..]) => {
+                if !self.groups.contains_key(*name) {
+                    return Err(not_found("group not found"));
+                }
+                self.handle_existing_group(method, name, rest, query, request)
+            },
+            _ => Err(not_found("unknown endpoint")),
+        }
+    }
+
+    fn handle_existing_group(&mut self, method: &Method, name: &str, path: &[&str], query: &HashMap<String, String>, request: &HttpRequest) -> Reply {
+        match (method, path) {
+            (&Method::GET, []) => Ok(json(StatusCode::OK, &self.group(name)?)),
+            (&Method::DELETE, []) => {
+                self.groups.remove(name);
+                Ok(empty(StatusCode::OK))
+            },
+            (_, ["nodes"]) => {
+                let nodes = self.groups.get_mut(name).ok_or_else(|| not_found("group not found"))?;
+                let nodes = handle_nodes(method, nodes, query, request)?;
+                Ok(json(StatusCode::OK, &nodes))
+            },
+            (&Method::GET, ["meta"]) => {
+                let nodes = self.resolve_nodes(&[inheritance(name)]);
+                Ok(json(StatusCode::OK, &metadata(&nodes)))
+            },
+            (_, ["permissionCheck"]) => {
+                self.permission_check(method, &self.groups[name], query, request)
+            },
+            _ => Err(not_found("unknown endpoint")),
+        }
+    }
+
+    fn handle_track(&mut self, method: &Method, path: &[&str], request: &HttpRequest) -> Reply {
+        match (method, path) {
+            (&Method::GET, []) => Ok(json(StatusCode::OK, &self.tracks.keys().collect::<Vec<_>>())),
+            (&Method::POST, []) => {
+                let create: TrackCreateRequest = body(request)?;
+                if self.tracks.contains_key(&create.name) {
+                    return Err(error(StatusCode::CONFLICT, "track already exists"));
+                }
+                self.tracks.insert(create.name.clone(), Vec::new());
+                Ok(json(StatusCode::CREATED, &Track {
+                    name: create.name,
+                    groups: Vec::new(),
+                }))
+            },
+            (_, [name]) => {
+                let groups = self.tracks.get_mut(*name).ok_or_else(|| not_found("track not found"))?;
+                match *method {
+                    Method::GET => Ok(json(StatusCode::OK, &Track {
+                        name: name.to_string(),
+                        groups: groups.clone(),
+                    })),
+                    Method::PATCH => {
+                        let update: TrackUpdateRequest = body(request)?;
+                        *groups = update.groups;
+                        Ok(empty(StatusCode::OK))
+                    },
+                    Method::DELETE => {
+                        self.tracks.remove(*name);
+                        Ok(empty(StatusCode::OK))
+                    },
+                    _ => Err(not_found("unknown endpoint")),
+                }
+            },
+            _ => Err(not_found("unknown endpoint")),
+        }
+    }
+
+    fn handle_action(&mut self, method: &Method, request: &HttpRequest) -> Reply {
+        if *method != Method::POST {
+            return Err(not_found("unknown endpoint"));
+        }
+
+        let mut action: Action = body(request)?;
+        action.timestamp.get_or_insert_with(now);
+        self.actions.push(action);
+        Ok(empty(StatusCode::OK))
+    }
+
+    fn handle_messaging(&mut self, method: &Method, path: &[&str], request: &HttpRequest) -> Reply {
+        match (method, path) {
+            (&Method::POST, ["update"]) => {
+                self.network_updates += 1;
+                Ok(empty(StatusCode::OK))
+            },
+            (&Method::POST, ["update", unique_id]) => {
+                parse_uuid(unique_id)?;
+                self.network_updates += 1;
+                Ok(empty(StatusCode::OK))
+            },
+            (&Method::POST, ["custom"]) => {
+                let message: CustomMessage = body(request)?;
+                self.custom_messages.push(message);
+                Ok(empty(StatusCode::OK))
+            },
+            _ => Err(not_found("unknown endpoint")),
+        }
+    }
+
+    fn permission_check(&self, method: &Method, own: &[Node], query: &HashMap<String, String>, request: &HttpRequest) -> Reply {
+        let (permission, contexts) = match *method {
+            Method::GET => {
+                let permission = query.get("permission")
+                    .ok_or_else(|| error(StatusCode::BAD_REQUEST, "missing permission"))?;
+                (permission.clone(), None)
+            },
+            Method::POST => {
+                let check: PermissionCheckRequest = body(request)?;
+                (check.permission, Some(check.query_options.contexts))
+            },
+            _ => return Err(not_found("unknown endpoint")),
+        };
+
+        let node = self.resolve_nodes(own).into_iter().find(|node| {
+            node.type_ == NodeType::Permission
+                && node.key == permission
+                && contexts.as_ref().map_or(node.context.is_empty(), |contexts| node.context.is_satisfied_by(contexts))
+        });
+
+        Ok(json(StatusCode::OK, &PermissionCheckResult {
+            result: node.as_ref().map_or(Tristate::Undefined, |node| node.value.into()),
+            node,
+        }))
+    }
+
+    /// Collect a holder's own nodes followed by those inherited from its groups, depth first.
+    fn resolve_nodes(&self, own: &[Node]) -> Vec<Node> {
+        let mut resolved = Vec::new();
+        let mut visited = HashSet::new();
+        self.collect_nodes(own, &mut resolved, &mut visited);
+        resolved
+    }
+
+    fn collect_nodes(&self, nodes: &[Node], resolved: &mut Vec<Node>, visited: &mut HashSet<String>) {
+        let now = now();
+        let active = nodes.iter().filter(|node| node.expiry.is_none_or(|expiry| expiry > now));
+        resolved.extend(active.clone().filter(|node| node.type_ != NodeType::Inheritance).cloned());
+
+        for node in active.filter(|node| node.type_ == NodeType::Inheritance && node.value) {
+            let Some(group) = parent_group(node) else { continue };
+            if visited.insert(group.to_string()) {
+                if let Some(parent_nodes) = self.groups.get(group) {
+                    resolved.push(node.clone());
+                    self.collect_nodes(parent_nodes, resolved, visited);
+                }
+            }
+        }
+    }
+
+    fn user(&self, unique_id: Uuid) -> Result<User, FakeError> {
+        let user = self.users.get(&unique_id).ok_or_else(|| not_found("user not found"))?;
+        Ok(User {
+            unique_id,
+            username: user.username.clone(),
+            parent_groups: user.nodes.iter().filter_map(parent_group).map(str::to_string).collect(),
+            nodes: user.nodes.clone(),
+        })
+    }
+
+    fn user_mut(&mut self, unique_id: Uuid) -> Result<&mut FakeUser, FakeError> {
+        self.users.get_mut(&unique_id).ok_or_else(|| not_found("user not found"))
+    }
+
+    fn group(&self, name: &str) -> Result<Group, FakeError> {
+        let nodes = self.groups.get(name).ok_or_else(|| not_found("group not found"))?;
+        let display_name = nodes.iter()
+            .find(|node| node.type_ == NodeType::DisplayName)
+            .and_then(|node| node.key.strip_prefix("displayname."))
+            .map(str::to_string);
+        let weight = nodes.iter()
+            .find(|node| node.type_ == NodeType::Weight)
+            .and_then(|node| node.key.strip_prefix("weight."))
+            .and_then(|weight| weight.parse().ok());
+
+        Ok(Group {
+            name: name.to_string(),
+            display_name,
+            weight,
+            nodes: nodes.clone(),
+            metadata: metadata(&self.resolve_nodes(&[inheritance(name)])),
+        })
+    }
+}
+
+fn handle_nodes(method: &Method, nodes: &mut Vec<Node>, query: &HashMap<String, String>, request: &HttpRequest) -> Result<Vec<Node>, FakeError> {
+    let strategy = query.get("temporaryNodeMergeStrategy")
+        .map(|strategy| serde_json::from_value(serde_json::Value::String(strategy.clone())))
+        .transpose()
+        .map_err(|_| error(StatusCode::BAD_REQUEST, "invalid temporaryNodeMergeStrategy"))?
+        .unwrap_or_default();
+
+    match *method {
+        Method::GET => {},
+        Method::POST => add_node(nodes, body(request)?, strategy),
+        Method::PATCH => {
+            for node in body::<Vec<Node>>(request)? {
+                add_node(nodes, node, strategy);
+            }
+        },
+        Method::PUT => *nodes = body(request)?,
+        Method::DELETE if request.body.is_none() => nodes.clear(),
+        Method::DELETE => {
+            let removed: Vec<Node> = body(request)?;
+            nodes.retain(|node| !removed.iter().any(|removed| same_node(node, removed)));
+        },
+        _ => return Err(not_found("unknown endpoint")),
+    }
+
+    Ok(nodes.clone())
+}
+
+fn add_node(nodes: &mut Vec<Node>, node: Node, strategy: TemporaryMergeStrategy) {
+    let existing = nodes.iter_mut().find(|existing| same_node(existing, &node));
+
+    match (existing, node.expiry) {
+        (None, _) => nodes.push(node),
+        (Some(existing), Some(expiry)) if existing.expiry.is_some() => match strategy {
+            TemporaryMergeStrategy::AddNewDurationToExisting => {
+                let duration = expiry.saturating_sub(now());
+                existing.expiry = existing.expiry.map(|existing| existing + duration);
+            },
+            TemporaryMergeStrategy::ReplaceExistingIfDurationLonger => {
+                if existing.expiry < Some(expiry) {
+                    *existing = node;
+                }
+            },
+            TemporaryMergeStrategy::None => nodes.push(node),
+        },
+        (Some(existing), _) => *existing = node,
+    }
+}
+
+fn same_node(a: &Node, b: &Node) -> bool {
+    a.key == b.key && a.value == b.value && a.context == b.context
+}
+
+fn search(nodes: &[Node], query: &HashMap<String, String>) -> Vec<Node> {
+    let type_ = query.get("type").map(|type_| type_.parse::<NodeType>().unwrap_or_else(|never| match never {}));
+
+    nodes.iter()
+        .filter(|node| type_.as_ref().is_none_or(|type_| node.type_ == *type_))
+        .filter(|node| {
+            if let Some(key) = query.get("key") {
+                node.key == *key
+            } else if let Some(prefix) = query.get("keyStartsWith") {
+                node.key.starts_with(prefix.as_str())
+            } else if let Some(meta_key) = query.get("metaKey") {
+                node.type_ == NodeType::Meta && node.key.starts_with(&format!("meta.{}.", meta_key))
+            } else {
+                false
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+/// Move a user one step along a track, following LuckPerms' status names.
+fn move_along_track(nodes: &mut Vec<Node>, track: &[String], promote: bool) -> TrackMoveResponse {
+    let on_track: Vec<usize> = track.iter().enumerate()
+        .filter(|(_, group)| nodes.iter().any(|node| parent_group(node) == Some(group.as_str()) && node.context.is_empty()))
+        .map(|(index, _)| index)
+        .collect();
+
+    let response = |success: bool, status: &str, from: Option<&String>, to: Option<&String>| TrackMoveResponse {
+        success,
+        status: status.to_string(),
+        group_from: from.cloned(),
+        group_to: to.cloned(),
+    };
+
+    if track.is_empty() {
+        return response(false, "malformed_track", None, None);
+    }
+    if on_track.len() > 1 {
+        return response(false, "ambiguous_call", None, None);
+    }
+
+    let remove = |nodes: &mut Vec<Node>, group: &str| nodes.retain(|node| parent_group(node) != Some(group));
+
+    match (on_track.first().copied(), promote) {
+        (None, true) => {
+            nodes.push(inheritance(&track[0]));
+            response(true, "added_to_first", None, Some(&track[0]))
+        },
+        (None, false) => response(false, "not_on_track", None, None),
+        (Some(index), true) if index + 1 == track.len() => response(false, "end_of_track", Some(&track[index]), None),
+        (Some(index), true) => {
+            remove(nodes, &track[index]);
+            nodes.push(inheritance(&track[index + 1]));
+            response(true, "success", Some(&track[index]), Some(&track[index + 1]))
+        },
+        (Some(0), false) => {
+            remove(nodes, &track[0]);
+            response(true, "removed_from_first", Some(&track[0]), None)
+        },
+        (Some(index), false) => {
+            remove(nodes, &track[index]);
+            nodes.push(inheritance(&track[index - 1]));
+            response(true, "success", Some(&track[index]), Some(&track[index - 1]))
+        },
+    }
+}
+
+fn metadata(nodes: &[Node]) -> Metadata {
+    let mut meta = HashMap::new();
+    let mut prefix: Option<(i64, String)> = None;
+    let mut suffix: Option<(i64, String)> = None;
+
+    for node in nodes.iter().filter(|node| node.value) {
+        match node.type_ {
+            NodeType::Meta => {
+                if let Some((key, value)) = node.key.strip_prefix("meta.").and_then(|meta| meta.split_once('.')) {
+                    meta.entry(key.to_string()).or_insert_with(|| value.to_string());
+                }
+            },
+            NodeType::Prefix | NodeType::Suffix => {
+                let slot = if node.type_ == NodeType::Prefix { &mut prefix } else { &mut suffix };
+                let Some((_, rest)) = node.key.split_once('.') else { continue };
+                let Some((priority, value)) = rest.split_once('.') else { continue };
+                let priority = priority.parse().unwrap_or(0);
+                if slot.as_ref().is_none_or(|(current, _)| priority > *current) {
+                    *slot = Some((priority, value.to_string()));
+                }
+            },
+            _ => {},
+        }
+    }
+
+    Metadata {
+        meta,
+        prefix: prefix.map(|(_, prefix)| prefix),
+        suffix: suffix.map(|(_, suffix)| suffix),
+        primary_group: nodes.iter().find_map(parent_group).map(str::to_string),
+    }
+}
+
+fn inheritance(group: &str) -> Node {
+    Node {
+        key: format!("group.{}", group),
+        type_: NodeType::Inheritance,
+        value: true,
+        context: ContextSet::new(),
+        expiry: None,
+    }
+}
+
+fn parent_group(node: &Node) -> Option<&str> {
+    match node.type_ {
+        NodeType::Inheritance => node.key.strip_prefix("group."),
+        _ => None,
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_secs())
+}
+
+fn percent_decode(value: &str) -> String {
+    form_urlencoded::parse(format!("v={}", value.replace('+', "%2B")).as_bytes())
+        .next()
+        .map(|(_, value)| value.into_owned())
+        .unwrap_or_default()
+}
+
+fn parse_uuid(value: &str) -> Result<Uuid, FakeError> {
+    Uuid::parse_str(value).map_err(|_| error(StatusCode::BAD_REQUEST, "invalid unique id"))
+}
+
+fn body<T: DeserializeOwned>(request: &HttpRequest) -> Result<T, FakeError> {
+    let body = request.body.as_deref().unwrap_or_default();
+    serde_json::from_slice(body).map_err(|e| error(StatusCode::BAD_REQUEST, &e.to_string()))
+}
+
+fn json<T: Serialize + ?Sized>(status: StatusCode, value: &T) -> HttpResponse {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+    HttpResponse {
+        status,
+        headers,
+        body: serde_json::to_vec(value).expect("fake responses are serializable"),
+    }
+}
+
+fn empty(status: StatusCode) -> HttpResponse {
+    HttpResponse {
+        status,
+        headers: HeaderMap::new(),
+        body: Vec::new(),
+    }
+}
+
+fn error(status: StatusCode, message: &str) -> FakeError {
+    FakeError {
+        status,
+        message: message.to_string(),
+    }
+}
+
+fn not_found(message: &str) -> FakeError {
+    error(StatusCode::NOT_FOUND, message)
+}
+
+fn error_response(error: FakeError) -> HttpResponse {
+    json(error.status, &serde_json::json!({ "message": error.message }))
+}
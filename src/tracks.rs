@@ -0,0 +1,141 @@
+use reqwest::Method;
+use serde::de::IgnoredAny;
+use crate::LuckClient;
+use crate::endpoint::{json_body, optional, Endpoint};
+use crate::errors::RequestError;
+use crate::models::{Track, TrackCreateRequest, TrackUpdateRequest};
+
+/// `GET /track`, the names of all tracks.
+#[derive(Debug, Clone)]
+pub struct ListTracks;
+
+impl Endpoint for ListTracks {
+    type Response = Vec<String>;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["track".to_string()]
+    }
+}
+
+/// `POST /track`, create a track.
+#[derive(Debug, Clone)]
+pub struct CreateTrack {
+    pub name: String,
+}
+
+impl Endpoint for CreateTrack {
+    type Response = Track;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["track".to_string()]
+    }
+
+    fn body(&self) -> Result<Option<Vec<u8>>, RequestError> {
+        json_body(&TrackCreateRequest {
+            name: self.name.clone(),
+        })
+    }
+}
+
+/// `GET /track/{name}`.
+#[derive(Debug, Clone)]
+pub struct GetTrack {
+    pub name: String,
+}
+
+impl Endpoint for GetTrack {
+    type Response = Track;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["track".to_string(), self.name.clone()]
+    }
+}
+
+/// `PATCH /track/{name}`, replace the groups on a track.
+#[derive(Debug, Clone)]
+pub struct UpdateTrack {
+    pub name: String,
+    pub groups: Vec<String>,
+}
+
+impl Endpoint for UpdateTrack {
+    type Response = IgnoredAny;
+
+    fn method(&self) -> Method {
+        Method::PATCH
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["track".to_string(), self.name.clone()]
+    }
+
+    fn body(&self) -> Result<Option<Vec<u8>>, RequestError> {
+        json_body(&TrackUpdateRequest {
+            groups: self.groups.clone(),
+        })
+    }
+
+    /// The groups are replaced, so sending the same update again changes nothing.
+    fn idempotent(&self) -> bool {
+        true
+    }
+}
+
+/// `DELETE /track/{name}`.
+#[derive(Debug, Clone)]
+pub struct DeleteTrack {
+    pub name: String,
+}
+
+impl Endpoint for DeleteTrack {
+    type Response = IgnoredAny;
+
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["track".to_string(), self.name.clone()]
+    }
+}
+
+impl LuckClient {
+    /// Get all track names.
+    pub async fn get_tracks(&self) -> Result<Vec<String>, RequestError> {
+        self.execute(ListTracks).await
+    }
+
+    /// Create a new track.
+    pub async fn create_track(&self, name: String) -> Result<Track, RequestError> {
+        self.execute(CreateTrack { name }).await
+    }
+
+    /// Get a track based on its name.
+    pub async fn get_track(&self, name: String) -> Result<Option<Track>, RequestError> {
+        optional(self.execute(GetTrack { name }).await)
+    }
+
+    /// Replace the ordered list of groups on a track.
+    pub async fn update_track(&self, name: String, groups: Vec<String>) -> Result<(), RequestError> {
+        self.execute(UpdateTrack { name, groups }).await?;
+        Ok(())
+    }
+
+    /// Delete a track based on its name.
+    pub async fn delete_track(&self, name: String) -> Result<(), RequestError> {
+        self.execute(DeleteTrack { name }).await?;
+        Ok(())
+    }
+}
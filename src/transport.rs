@@ -0,0 +1,165 @@
+use std::fmt::Debug;
+use std::time::Duration;
+use futures_util::future::BoxFuture;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::{Client, Method, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use crate::errors::RequestError;
+
+/// An HTTP request to the LuckPerms instance.
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap,
+    pub body: Option<Vec<u8>>,
+}
+
+impl HttpRequest {
+    pub fn new(method: Method, url: Url) -> Self {
+        Self {
+            method,
+            url,
+            headers: HeaderMap::new(),
+            body: None,
+        }
+    }
+
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Set a JSON body.
+    pub fn json<T: Serialize + ?Sized>(mut self, body: &T) -> Result<Self, RequestError> {
+        self.body = Some(serde_json::to_vec(body)?);
+        self.headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        Ok(self)
+    }
+}
+
+/// A fully read HTTP response.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, RequestError> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// An HTTP response whose body is read incrementally, used for event streams.
+pub struct StreamingResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: BoxStream<'static, Result<Vec<u8>, RequestError>>,
+}
+
+impl StreamingResponse {
+    /// Read the whole body, e.g. to inspect an error response.
+    pub async fn collect(self) -> Result<HttpResponse, RequestError> {
+        let mut body = Vec::new();
+        let mut chunks = self.body;
+        while let Some(chunk) = chunks.next().await {
+            body.extend_from_slice(&chunk?);
+        }
+
+        Ok(HttpResponse {
+            status: self.status,
+            headers: self.headers,
+            body,
+        })
+    }
+}
+
+impl From<HttpResponse> for StreamingResponse {
+    fn from(response: HttpResponse) -> Self {
+        Self {
+            status: response.status,
+            headers: response.headers,
+            body: stream::once(async move { Ok(response.body) }).boxed(),
+        }
+    }
+}
+
+/// Sends HTTP requests on behalf of a [`crate::LuckClient`].
+///
+/// The default implementation is [`ReqwestTransport`]. Other implementations can be
+/// used for in-memory fakes, recording proxies or other HTTP stacks.
+pub trait Transport: Debug + Send + Sync {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, RequestError>>;
+
+    /// Send a request and stream the response body.
+    ///
+    /// By default this reads the whole response with [`Transport::send`], which is
+    /// enough for transports that don't hold connections open.
+    fn stream(&self, request: HttpRequest) -> BoxFuture<'_, Result<StreamingResponse, RequestError>> {
+        Box::pin(async move { Ok(self.send(request).await?.into()) })
+    }
+}
+
+/// A [`Transport`] backed by a `reqwest::Client`.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: Client,
+    timeout: Option<Duration>,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: Client) -> Self {
+        Self { client, timeout: None }
+    }
+
+    /// Timeout for an entire request sent with [`Transport::send`]. Streamed responses are exempt.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    async fn execute(&self, request: HttpRequest, timeout: Option<Duration>) -> Result<reqwest::Response, RequestError> {
+        let mut builder = self.client.request(request.method, request.url).headers(request.headers);
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+        Ok(builder.send().await?)
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, RequestError>> {
+        Box::pin(async move {
+            let response = self.execute(request, self.timeout).await?;
+            Ok(HttpResponse {
+                status: response.status(),
+                headers: response.headers().clone(),
+                body: response.bytes().await?.to_vec(),
+            })
+        })
+    }
+
+    fn stream(&self, request: HttpRequest) -> BoxFuture<'_, Result<StreamingResponse, RequestError>> {
+        Box::pin(async move {
+            let response = self.execute(request, None).await?;
+            Ok(StreamingResponse {
+                status: response.status(),
+                headers: response.headers().clone(),
+                body: response.bytes_stream()
+                    .map(|chunk| chunk.map(|chunk| chunk.to_vec()).map_err(RequestError::from))
+                    .boxed(),
+            })
+        })
+    }
+}
\ No newline at end of file
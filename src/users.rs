@@ -1,193 +1,282 @@
-use reqwest::StatusCode;
+use reqwest::Method;
+use serde::de::IgnoredAny;
 use uuid::Uuid;
-use crate::{LuckClient, models};
+use crate::LuckClient;
+use crate::holders::HolderRef;
+use crate::endpoint::{json_body, optional, Endpoint};
 use crate::errors::RequestError;
-use crate::models::{PermissionCheckResult, TrackMoveRequest, TrackMoveResponse, User, UserIdentifier, UsernameUpdateRequest, UserSearchResult};
-use crate::requests::PermissionCheckRequest;
+use crate::models::{Metadata, Node, PermissionCheckResult, TemporaryMergeStrategy, TrackMoveRequest, TrackMoveResponse, User, UserIdentifier, UsernameUpdateRequest, UserSearchResult};
+use crate::requests::{PermissionCheckRequest, SearchRequest};
+
+/// `GET /user`, the uuids of all users.
+#[derive(Debug, Clone)]
+pub struct ListUsers;
+
+impl Endpoint for ListUsers {
+    type Response = Vec<Uuid>;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["user".to_string()]
+    }
+}
+
+/// `POST /user`, create a user.
+#[derive(Debug, Clone)]
+pub struct CreateUser {
+    pub user: UserIdentifier,
+}
+
+impl Endpoint for CreateUser {
+    type Response = User;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["user".to_string()]
+    }
+
+    fn body(&self) -> Result<Option<Vec<u8>>, RequestError> {
+        json_body(&self.user)
+    }
+}
+
+/// `GET /user/lookup`, find a user by username or uuid.
+#[derive(Debug, Clone)]
+pub enum LookupUser {
+    Username(String),
+    UniqueId(Uuid),
+}
+
+impl Endpoint for LookupUser {
+    type Response = UserIdentifier;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["user".to_string(), "lookup".to_string()]
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Self::Username(username) => vec![("username", username.clone())],
+            Self::UniqueId(uuid) => vec![("uniqueId", uuid.to_string())],
+        }
+    }
+}
+
+/// `GET /user/search`, find users with matching nodes.
+#[derive(Debug, Clone)]
+pub struct SearchUsers {
+    pub search: SearchRequest,
+}
+
+impl Endpoint for SearchUsers {
+    type Response = Vec<UserSearchResult>;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["user".to_string(), "search".to_string()]
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        self.search.query_pairs()
+    }
+}
+
+/// `GET /user/{uuid}`.
+#[derive(Debug, Clone)]
+pub struct GetUser {
+    pub uuid: Uuid,
+}
+
+impl Endpoint for GetUser {
+    type Response = User;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["user".to_string(), self.uuid.to_string()]
+    }
+}
+
+/// `PATCH /user/{uuid}`, change a user's username.
+#[derive(Debug, Clone)]
+pub struct UpdateUsername {
+    pub uuid: Uuid,
+    pub username: String,
+}
+
+impl Endpoint for UpdateUsername {
+    type Response = IgnoredAny;
+
+    fn method(&self) -> Method {
+        Method::PATCH
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["user".to_string(), self.uuid.to_string()]
+    }
+
+    fn body(&self) -> Result<Option<Vec<u8>>, RequestError> {
+        json_body(&UsernameUpdateRequest {
+            username: self.username.clone(),
+        })
+    }
+
+    /// Setting the same username again changes nothing.
+    fn idempotent(&self) -> bool {
+        true
+    }
+}
+
+/// `DELETE /user/{uuid}`.
+#[derive(Debug, Clone)]
+pub struct DeleteUser {
+    pub uuid: Uuid,
+}
+
+impl Endpoint for DeleteUser {
+    type Response = IgnoredAny;
+
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["user".to_string(), self.uuid.to_string()]
+    }
+}
+
+/// `POST /user/{uuid}/promote` or `/demote`, move a user along a track.
+#[derive(Debug, Clone)]
+pub struct MoveUserAlongTrack {
+    pub uuid: Uuid,
+    pub track: String,
+    pub promote: bool,
+}
+
+impl Endpoint for MoveUserAlongTrack {
+    type Response = TrackMoveResponse;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path(&self) -> Vec<String> {
+        let direction = if self.promote { "promote" } else { "demote" };
+        vec!["user".to_string(), self.uuid.to_string(), direction.to_string()]
+    }
+
+    fn body(&self) -> Result<Option<Vec<u8>>, RequestError> {
+        json_body(&TrackMoveRequest {
+            track: self.track.clone(),
+        })
+    }
+}
 
 impl LuckClient {
     /// Get a list of all users on the LuckPerms instance.
     pub async fn users(&self) -> Result<Vec<Uuid>, RequestError> {
-        let url = self.base_url.join("/user")?;
-        let response = self.client.get(url).send().await?;
-        let users: Vec<Uuid> = response.json().await?;
-        Ok(users)
+        self.execute(ListUsers).await
     }
 
     /// Create a new user on the LuckPerms instance.
     pub async fn create_user(&self, user: UserIdentifier) -> Result<User, RequestError> {
-        let url = self.base_url.join("/user")?;
-        let response = self.client.post(url).json(&user).send().await?;
-        let user: User = response.error_for_status()?.json().await?;
-
-        Ok(user)
+        self.execute(CreateUser { user }).await
     }
 
     /// Lookup a user based on a username from the LuckPerms instance.
     pub async fn username_lookup(&self, username: String) -> Result<UserIdentifier, RequestError> {
-        let mut url = self.base_url.join("/user/lookup")?;
-        url.query_pairs_mut().append_pair("username", &username);
-        let response = self.client.get(url).send().await?;
-        let user: UserIdentifier = response.error_for_status()?.json().await?;
-
-        Ok(user)
+        self.execute(LookupUser::Username(username)).await
     }
 
     /// Lookup a user based on a UUID from the LuckPerms instance.
     pub async fn uuid_lookup(&self, uuid: Uuid) -> Result<UserIdentifier, RequestError> {
-        let mut url = self.base_url.join("/user/lookup")?;
-        url.query_pairs_mut().append_pair("uniqueId", &uuid.to_string());
-        let response = self.client.get(url).send().await?;
-        let user: UserIdentifier = response.error_for_status()?.json().await?;
-
-        Ok(user)
+        self.execute(LookupUser::UniqueId(uuid)).await
     }
 
     /// Search for users matching certain nodes.
-    pub async fn user_search(&self, search: crate::requests::SearchRequest) -> Result<Vec<UserSearchResult>, RequestError> {
-        let mut url = self.base_url.join("/user/search")?;
-        let query = url.query_pairs_mut();
-        search.set_query_values(query);
-        let response = self.client.get(url).send().await?;
-        let users: Vec<UserSearchResult> = response.error_for_status()?.json().await?;
-
-        Ok(users)
+    pub async fn user_search(&self, search: SearchRequest) -> Result<Vec<UserSearchResult>, RequestError> {
+        self.execute(SearchUsers { search }).await
     }
 
     /// Get a user based on their UUID.
     pub async fn get_user(&self, uuid: Uuid) -> Result<Option<User>, RequestError> {
-        let url = self.base_url.join(&format!("/user/{}", uuid))?;
-        let response = self.client.get(url).send().await?;
-
-        let status = response.status();
-
-        if status == StatusCode::NOT_FOUND {
-            return Ok(None);
-        }
-
-        let user: User = response.error_for_status()?.json().await?;
-
-        Ok(Some(user))
+        optional(self.execute(GetUser { uuid }).await)
     }
 
     /// Update a user's username.
     pub async fn update_username(&self, uuid: Uuid, username: String) -> Result<(), RequestError> {
-        let url = self.base_url.join(&format!("/user/{}", uuid))?;
-        let response = self.client.patch(url).json(&UsernameUpdateRequest {
-            username,
-        }).send().await?;
-
-        response.error_for_status()?;
-
+        self.execute(UpdateUsername { uuid, username }).await?;
         Ok(())
     }
 
     /// Delete a user from the LuckPerms instance.
     pub async fn delete_user(&self, uuid: Uuid) -> Result<(), RequestError> {
-        let url = self.base_url.join(&format!("/user/{}", uuid))?;
-        let response = self.client.delete(url).send().await?;
-
-        response.error_for_status()?;
-
+        self.execute(DeleteUser { uuid }).await?;
         Ok(())
     }
 
     /// Get all nodes for a user.
-    pub async fn get_user_nodes(&self, uuid: Uuid) -> Result<Vec<models::Node>, RequestError> {
-        let url = self.base_url.join(&format!("/user/{}/nodes", uuid))?;
-        let response = self.client.get(url).send().await?;
-        let nodes: Vec<models::Node> = response.error_for_status()?.json().await?;
-
-        Ok(nodes)
+    pub async fn get_user_nodes(&self, uuid: Uuid) -> Result<Vec<Node>, RequestError> {
+        HolderRef::User(uuid).get_nodes(self).await
     }
 
     /// Add a node to a user.
-    pub async fn add_user_node(&self, uuid: Uuid, node: models::Node) -> Result<(), RequestError> {
-        let url = self.base_url.join(&format!("/user/{}/nodes", uuid))?;
-        let response = self.client.post(url).json(&node).send().await?;
-
-        response.error_for_status()?;
-
-        Ok(())
+    pub async fn add_user_node(&self, uuid: Uuid, node: Node, merge_strategy: TemporaryMergeStrategy) -> Result<Vec<Node>, RequestError> {
+        HolderRef::User(uuid).add_node(self, node, merge_strategy).await
     }
 
     /// Add multiple nodes to a user.
-    pub async fn add_user_nodes(&self, uuid: Uuid, nodes: Vec<models::Node>) -> Result<(), RequestError> {
-        let url = self.base_url.join(&format!("/user/{}/nodes", uuid))?;
-        let response = self.client.patch(url).json(&nodes).send().await?;
-
-        response.error_for_status()?;
-
-        Ok(())
+    pub async fn add_user_nodes(&self, uuid: Uuid, nodes: Vec<Node>, merge_strategy: TemporaryMergeStrategy) -> Result<Vec<Node>, RequestError> {
+        HolderRef::User(uuid).add_nodes(self, nodes, merge_strategy).await
     }
 
     /// Set a user's nodes.
-    pub async fn set_user_nodes(&self, uuid: Uuid, nodes: Vec<models::Node>) -> Result<(), RequestError> {
-        let url = self.base_url.join(&format!("/user/{}/nodes", uuid))?;
-        let response = self.client.put(url).json(&nodes).send().await?;
-
-        response.error_for_status()?;
-
-        Ok(())
+    pub async fn set_user_nodes(&self, uuid: Uuid, nodes: Vec<Node>) -> Result<(), RequestError> {
+        HolderRef::User(uuid).set_nodes(self, nodes).await
     }
 
     /// Delete nodes from a user.
-    pub async fn delete_user_nodes(&self, uuid: Uuid, nodes: Vec<models::Node>) -> Result<(), RequestError> {
-        let url = self.base_url.join(&format!("/user/{}/nodes", uuid))?;
-        let response = self.client.delete(url).json(&nodes).send().await?;
-
-        response.error_for_status()?;
-
-        Ok(())
+    pub async fn delete_user_nodes(&self, uuid: Uuid, nodes: Vec<Node>) -> Result<(), RequestError> {
+        HolderRef::User(uuid).delete_nodes(self, nodes).await
     }
 
     /// Get a user's meta data.
-    pub async fn get_user_metadata(&self, uuid: Uuid) -> Result<Vec<models::Metadata>, RequestError> {
-        let url = self.base_url.join(&format!("/user/{}/meta", uuid))?;
-        let response = self.client.get(url).send().await?;
-        let metadata: Vec<models::Metadata> = response.error_for_status()?.json().await?;
-
-        Ok(metadata)
+    pub async fn get_user_metadata(&self, uuid: Uuid) -> Result<Metadata, RequestError> {
+        HolderRef::User(uuid).metadata(self).await
     }
 
     /// Check if a user has a permission.
     pub async fn check_user_permission(&self, uuid: Uuid, permission: String) -> Result<PermissionCheckResult, RequestError> {
-        let mut url = self.base_url.join(&format!("/user/{}/permissionCheck", uuid))?;
-        url.query_pairs_mut().append_pair("permission", &permission);
-        let response = self.client.get(url).send().await?;
-        let result: PermissionCheckResult = response.error_for_status()?.json().await?;
-
-        Ok(result)
+        HolderRef::User(uuid).check_permission(self, permission).await
     }
 
     /// Check if a user has a permission with more specific query options.
     pub async fn check_user_permission_query(&self, uuid: Uuid, request: PermissionCheckRequest) -> Result<PermissionCheckResult, RequestError> {
-        let url = self.base_url.join(&format!("/user/{}/permissionCheck", uuid))?;
-        let response = self.client.post(url).json(&request).send().await?;
-        let result: PermissionCheckResult = response.error_for_status()?.json().await?;
-
-        Ok(result)
+        HolderRef::User(uuid).check_permission_query(self, request).await
     }
 
     /// Promote a user along a track.
     pub async fn promote_user(&self, uuid: Uuid, track: String) -> Result<TrackMoveResponse, RequestError> {
-        let url = self.base_url.join(&format!("/user/{}/promote", uuid))?;
-        let response = self.client.post(url).json(&TrackMoveRequest {
-            track,
-        }).send().await?;
-
-
-
-        Ok(response.error_for_status()?.json().await?)
+        self.execute(MoveUserAlongTrack { uuid, track, promote: true }).await
     }
 
     /// Demote a user along a track.
     pub async fn demote_user(&self, uuid: Uuid, track: String) -> Result<TrackMoveResponse, RequestError> {
-        let url = self.base_url.join(&format!("/user/{}/demote", uuid))?;
-        let response = self.client.post(url).json(&TrackMoveRequest {
-            track,
-        }).send().await?;
-
-        Ok(response.error_for_status()?.json().await?)
+        self.execute(MoveUserAlongTrack { uuid, track, promote: false }).await
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,19 @@
+#![cfg(all(feature = "blocking", feature = "testing"))]
+
+use luckperms_rs::blocking::LuckClient;
+use luckperms_rs::testing::FakeLuckPerms;
+use uuid::Uuid;
+
+#[test]
+fn blocking_client_calls_endpoints() {
+    let fake = FakeLuckPerms::new();
+    let client = LuckClient::from_async(fake.client()).unwrap();
+    let uuid = Uuid::new_v4();
+    fake.insert_user(uuid, "notch");
+
+    assert_eq!(client.get_user(uuid).unwrap().unwrap().username, "notch");
+    assert_eq!(client.create_group("vip".to_string()).unwrap().name, "vip");
+    assert!(client.get_groups().unwrap().contains(&"vip".to_string()));
+    assert!(client.get_group("missing".to_string()).unwrap().is_none());
+    assert!(client.health().unwrap().healthy);
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use common::Stub;
+use luckperms_rs::breaker::{CircuitBreaker, CircuitState};
+use luckperms_rs::errors::RequestError;
+use luckperms_rs::LuckClient;
+use reqwest::StatusCode;
+
+/// Fails the first `/group` request, and hangs on the first `/health` probe.
+fn flaky() -> (Stub, Arc<AtomicUsize>) {
+    let groups = Arc::new(AtomicUsize::new(0));
+    let probes = Arc::new(AtomicUsize::new(0));
+    let stub = Stub::with_future({
+        let probes = probes.clone();
+        move |request, _| {
+            let (status, body) = if request.url.path() == "/health" {
+                if probes.fetch_add(1, Ordering::SeqCst) == 0 {
+                    return Box::pin(futures_util::future::pending());
+                }
+                (StatusCode::OK, r#"{"healthy":true}"#)
+            } else if groups.fetch_add(1, Ordering::SeqCst) == 0 {
+                (StatusCode::SERVICE_UNAVAILABLE, "")
+            } else {
+                (StatusCode::OK, "[]")
+            };
+            Box::pin(async move { Ok(common::response(status, body)) })
+        }
+    });
+    (stub, probes)
+}
+
+#[tokio::test]
+async fn cancelled_probe_lets_a_later_request_probe_again() {
+    let (transport, probes) = flaky();
+    let client = common::builder(transport)
+        .circuit_breaker(CircuitBreaker::new(1, Duration::ZERO))
+        .build()
+        .unwrap();
+
+    assert!(matches!(client.get_groups().await, Err(RequestError::Server { .. })));
+
+    let cancelled = tokio::time::timeout(Duration::from_millis(50), client.get_groups()).await;
+    assert!(cancelled.is_err());
+    assert_eq!(probes.load(Ordering::SeqCst), 1);
+
+    assert_eq!(client.get_groups().await.unwrap(), Vec::<String>::new());
+    assert_eq!(probes.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn state_change_callback_can_read_the_state() {
+    let client_slot: Arc<OnceLock<LuckClient>> = Arc::default();
+    let seen: Arc<Mutex<Vec<Option<CircuitState>>>> = Arc::default();
+    let breaker = CircuitBreaker::new(1, Duration::from_secs(60)).on_state_change({
+        let client_slot = client_slot.clone();
+        let seen = seen.clone();
+        move |_, _| seen.lock().unwrap().push(client_slot.get().and_then(LuckClient::circuit_state))
+    });
+
+    let client = common::builder(flaky().0)
+        .circuit_breaker(breaker)
+        .build()
+        .unwrap();
+    client_slot.set(client.clone()).ok().unwrap();
+
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+    assert!(client.get_groups().await.is_err());
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+    assert_eq!(*seen.lock().unwrap(), vec![Some(CircuitState::Open)]);
+}
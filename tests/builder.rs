@@ -0,0 +1,19 @@
+use std::net::TcpListener;
+use std::time::Duration;
+use luckperms_rs::LuckClient;
+
+#[tokio::test]
+async fn timeout_applies_to_a_provided_client() {
+    // Accepts connections but never answers them.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let client = LuckClient::builder(base_url, "key".to_string())
+        .client(reqwest::Client::new())
+        .timeout(Duration::from_millis(200))
+        .build()
+        .unwrap();
+
+    let result = tokio::time::timeout(Duration::from_secs(5), client.get_groups()).await;
+    assert!(matches!(result, Ok(Err(_))), "the request was not timed out");
+    drop(listener);
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use std::time::Duration;
+use common::Stub;
+use futures_util::stream::StreamExt;
+use luckperms_rs::cassette::Cassette;
+use luckperms_rs::errors::RequestError;
+use luckperms_rs::events::{Event, EventKind};
+use luckperms_rs::models::NodeType;
+use luckperms_rs::LuckClient;
+use reqwest::StatusCode;
+use uuid::Uuid;
+
+const NOTCH: Uuid = uuid::uuid!("069a79f4-44e9-4726-a5be-fca90e38aaf5");
+
+fn replay(cassette: &str) -> LuckClient {
+    let path = format!("{}/tests/cassettes/{}.json", env!("CARGO_MANIFEST_DIR"), cassette);
+    LuckClient::builder("http://localhost:8080".to_string(), "key".to_string())
+        .replay_cassette(path)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn user_wire_format() {
+    let user = replay("user").get_user(NOTCH).await.unwrap().unwrap();
+
+    assert_eq!(user.unique_id, NOTCH);
+    assert_eq!(user.username, "notch");
+    assert_eq!(user.parent_groups, ["default", "vip"]);
+    assert_eq!(user.nodes.len(), 3);
+    assert_eq!(user.nodes[1].type_, NodeType::Inheritance);
+    assert_eq!(user.nodes[1].expiry, Some(1893456000));
+
+    let fly = &user.nodes[2];
+    assert_eq!(fly.type_, NodeType::Permission);
+    assert!(fly.context.contains("world", "nether"));
+    assert_eq!(fly.context.get("server").collect::<Vec<_>>(), ["survival"]);
+}
+
+#[tokio::test]
+async fn group_wire_format() {
+    let client = replay("group");
+    let group = client.get_group("admin".to_string()).await.unwrap().unwrap();
+
+    assert_eq!(group.name, "admin");
+    assert_eq!(group.display_name.as_deref(), Some("Administrator"));
+    assert_eq!(group.weight, Some(100));
+    assert_eq!(group.nodes[1].type_, NodeType::Weight);
+    assert_eq!(group.nodes[2].type_, NodeType::Prefix);
+    assert!(group.nodes[3].context.contains("server", "creative"));
+    assert_eq!(group.metadata.prefix.as_deref(), Some("[Admin]"));
+    assert_eq!(group.metadata.meta.get("rank").map(String::as_str), Some("3"));
+
+    assert!(client.get_group("missing".to_string()).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn track_move_wire_format() {
+    let client = replay("track_moves");
+
+    let first = client.promote_user(NOTCH, "staff".to_string()).await.unwrap();
+    assert_eq!(first.status, "added_to_first");
+    assert_eq!(first.group_from, None);
+    assert_eq!(first.group_to.as_deref(), Some("helper"));
+
+    let second = client.promote_user(NOTCH, "staff".to_string()).await.unwrap();
+    assert_eq!(second.group_from.as_deref(), Some("helper"));
+    assert_eq!(second.group_to.as_deref(), Some("mod"));
+
+    let demoted = client.demote_user(NOTCH, "staff".to_string()).await.unwrap();
+    assert!(demoted.success);
+    assert_eq!(demoted.group_to.as_deref(), Some("helper"));
+}
+
+#[tokio::test]
+async fn unmatched_request_fails() {
+    let client = replay("user");
+
+    let error = client.get_user(Uuid::nil()).await.unwrap_err();
+    assert!(matches!(error, RequestError::Transport(_)));
+    assert!(error.to_string().contains("No recorded interaction matches GET /user/00000000-0000-0000-0000-000000000000"));
+}
+
+#[cfg(feature = "testing")]
+#[tokio::test]
+async fn record_then_replay() {
+    use luckperms_rs::testing::FakeLuckPerms;
+
+    let path = std::env::temp_dir().join(format!("luckperms-rs-cassette-{}.json", Uuid::new_v4()));
+    let fake = FakeLuckPerms::new();
+    fake.insert_user(NOTCH, "notch");
+
+    let recording = LuckClient::builder("http://luckperms.test".to_string(), "key".to_string())
+        .transport(fake)
+        .record_cassette(&path)
+        .build()
+        .unwrap();
+    let recorded = recording.get_user(NOTCH).await.unwrap().unwrap();
+    recording.create_group("vip".to_string()).await.unwrap();
+
+    let replaying = LuckClient::builder("http://other-host:8080".to_string(), "key".to_string())
+        .replay_cassette(&path)
+        .build()
+        .unwrap();
+    let replayed = replaying.get_user(NOTCH).await.unwrap().unwrap();
+    assert_eq!(replayed.nodes, recorded.nodes);
+    assert_eq!(replaying.create_group("vip".to_string()).await.unwrap().name, "vip");
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn recording_passes_event_streams_through() {
+    let path = std::env::temp_dir().join(format!("luckperms-rs-cassette-{}.json", Uuid::new_v4()));
+    // Streams a single event over a connection that then stays open.
+    let open = Stub::streaming(|_, _| Ok(common::event_stream(&["data: {}\n\n"], true)));
+    let client = common::builder(open)
+        .record_cassette(&path)
+        .build()
+        .unwrap();
+
+    let mut events = client.events_of(&[EventKind::PreSync]).await.unwrap();
+    let event = tokio::time::timeout(Duration::from_secs(5), events.next()).await.unwrap();
+    assert!(matches!(event, Some(Ok(Event::PreSync))));
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn paths_are_recorded_relative_to_the_base_url() {
+    let path = std::env::temp_dir().join(format!("luckperms-rs-cassette-{}.json", Uuid::new_v4()));
+    let recording = LuckClient::builder("http://luckperms.test/luckperms/".to_string(), "key".to_string())
+        .transport(Stub::always(StatusCode::OK, "[]"))
+        .record_cassette(&path)
+        .build()
+        .unwrap();
+    recording.get_groups().await.unwrap();
+    recording.get_tracks().await.unwrap();
+
+    let cassette = Cassette::load(&path).unwrap();
+    let paths: Vec<&str> = cassette.interactions.iter().map(|interaction| interaction.request.path.as_str()).collect();
+    assert_eq!(paths, ["/group", "/track"]);
+
+    for base_url in ["http://other-host/", "http://other-host/api/lp"] {
+        let replaying = LuckClient::builder(base_url.to_string(), "key".to_string())
+            .replay_cassette(&path)
+            .build()
+            .unwrap();
+        assert!(replaying.get_groups().await.unwrap().is_empty());
+        assert!(replaying.get_tracks().await.unwrap().is_empty());
+    }
+
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "testing")]
+
+use luckperms_rs::models::{Context, ContextSet, Node, NodeType, Tristate};
+use luckperms_rs::requests::PermissionCheckRequest;
+use luckperms_rs::testing::FakeLuckPerms;
+
+fn survival() -> ContextSet {
+    [Context::new("server", "survival")].into_iter().collect()
+}
+
+fn fake() -> FakeLuckPerms {
+    let fake = FakeLuckPerms::new();
+    fake.insert_group("builder", vec![Node {
+        key: "worldedit.wand".to_string(),
+        type_: NodeType::Permission,
+        value: true,
+        context: survival(),
+        expiry: None,
+    }]);
+    fake
+}
+
+#[tokio::test]
+async fn child_uses_default_contexts() {
+    let client = fake().client();
+    let child = client.with_default_contexts(survival());
+
+    let check = |client: luckperms_rs::LuckClient| async move {
+        client.check_group_permission("builder".to_string(), "worldedit.wand".to_string()).await.unwrap().result
+    };
+    assert_eq!(check(client.clone()).await, Tristate::Undefined);
+    assert_eq!(check(child.clone()).await, Tristate::True);
+    assert!(client.default_contexts().is_empty());
+
+    let mut creative = PermissionCheckRequest::new("worldedit.wand");
+    creative.query_options.contexts.insert("server", "creative");
+    let result = child.check_group_permission_query("builder".to_string(), creative).await.unwrap();
+    assert_eq!(result.result, Tristate::Undefined);
+}
+
+#[tokio::test]
+async fn clones_share_state() {
+    let fake = fake();
+    let client = fake.client();
+    let child = client.with_api_key("other".to_string()).unwrap();
+
+    let clone = client.clone();
+    tokio::spawn(async move { clone.create_group("vip".to_string()).await.unwrap() }).await.unwrap();
+
+    assert!(child.get_group("vip".to_string()).await.unwrap().is_some());
+    assert!(client.with_api_key("invalid\nkey".to_string()).is_err());
+}
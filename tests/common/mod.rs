@@ -0,0 +1,145 @@
+//! Helpers shared by the integration tests.
+#![allow(dead_code)]
+
+use std::fmt::{self, Debug};
+use std::sync::{Arc, Mutex};
+use futures_util::future::BoxFuture;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use luckperms_rs::builder::LuckClientBuilder;
+use luckperms_rs::errors::RequestError;
+use luckperms_rs::transport::{HttpRequest, HttpResponse, StreamingResponse, Transport};
+use luckperms_rs::LuckClient;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+
+type Reply<T> = BoxFuture<'static, Result<T, RequestError>>;
+type Respond<T> = Box<dyn Fn(&HttpRequest, usize) -> Reply<T> + Send + Sync>;
+
+enum Handler {
+    Send(Respond<HttpResponse>),
+    Stream(Respond<StreamingResponse>),
+}
+
+/// A transport answering requests with a closure, keeping every request it was sent.
+///
+/// The closure is given each request along with how many requests were sent before it.
+#[derive(Clone)]
+pub struct Stub {
+    handler: Arc<Handler>,
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+impl Stub {
+    /// Answer requests with `respond`.
+    pub fn new(respond: impl Fn(&HttpRequest, usize) -> Result<HttpResponse, RequestError> + Send + Sync + 'static) -> Self {
+        Self::with_future(move |request, sent| {
+            let reply = respond(request, sent);
+            Box::pin(async move { reply })
+        })
+    }
+
+    /// Answer requests with the future returned by `respond`, e.g. to answer slowly or never.
+    pub fn with_future(respond: impl Fn(&HttpRequest, usize) -> Reply<HttpResponse> + Send + Sync + 'static) -> Self {
+        Self::with_handler(Handler::Send(Box::new(respond)))
+    }
+
+    /// Answer every request with the same status and body.
+    pub fn always(status: StatusCode, body: &'static str) -> Self {
+        Self::new(move |_, _| Ok(response(status, body)))
+    }
+
+    /// Answer event streams with `respond`. Requests sent with [`Transport::send`] panic.
+    pub fn streaming(respond: impl Fn(&HttpRequest, usize) -> Result<StreamingResponse, RequestError> + Send + Sync + 'static) -> Self {
+        Self::with_handler(Handler::Stream(Box::new(move |request, sent| {
+            let reply = respond(request, sent);
+            Box::pin(async move { reply })
+        })))
+    }
+
+    fn with_handler(handler: Handler) -> Self {
+        Self {
+            handler: Arc::new(handler),
+            requests: Arc::default(),
+        }
+    }
+
+    /// Every request sent so far.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// How many requests were sent so far.
+    pub fn sent(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+
+    /// Forget the requests sent so far.
+    pub fn reset(&self) {
+        self.requests.lock().unwrap().clear();
+    }
+
+    /// Keep `request`, returning how many requests were sent before it.
+    fn record(&self, request: &HttpRequest) -> usize {
+        let mut requests = self.requests.lock().unwrap();
+        requests.push(request.clone());
+        requests.len() - 1
+    }
+}
+
+impl Debug for Stub {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stub").field("sent", &self.sent()).finish_non_exhaustive()
+    }
+}
+
+impl Transport for Stub {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, RequestError>> {
+        let sent = self.record(&request);
+        match &*self.handler {
+            Handler::Send(respond) => respond(&request, sent),
+            Handler::Stream(_) => panic!("event streams must be read with `stream`, not `send`"),
+        }
+    }
+
+    fn stream(&self, request: HttpRequest) -> BoxFuture<'_, Result<StreamingResponse, RequestError>> {
+        let sent = self.record(&request);
+        match &*self.handler {
+            Handler::Send(respond) => {
+                let reply = respond(&request, sent);
+                Box::pin(async move { Ok(reply.await?.into()) })
+            },
+            Handler::Stream(respond) => respond(&request, sent),
+        }
+    }
+}
+
+pub fn response(status: StatusCode, body: &str) -> HttpResponse {
+    HttpResponse {
+        status,
+        headers: HeaderMap::new(),
+        body: body.as_bytes().to_vec(),
+    }
+}
+
+/// A successful event stream yielding `chunks`, then staying open if `open` is set.
+pub fn event_stream(chunks: &[&'static str], open: bool) -> StreamingResponse {
+    let chunks = stream::iter(chunks.iter().map(|chunk| Ok(chunk.as_bytes().to_vec())).collect::<Vec<_>>());
+    let body: BoxStream<'static, _> = if open {
+        chunks.chain(stream::pending()).boxed()
+    } else {
+        chunks.boxed()
+    };
+    StreamingResponse {
+        status: StatusCode::OK,
+        headers: HeaderMap::new(),
+        body,
+    }
+}
+
+pub fn builder(transport: impl Transport + 'static) -> LuckClientBuilder {
+    LuckClient::builder("http://localhost:8080".to_string(), "key".to_string()).transport(transport)
+}
+
+pub fn client(transport: impl Transport + 'static) -> LuckClient {
+    builder(transport).build().unwrap()
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "testing")]
+
+use luckperms_rs::endpoint::Endpoint;
+use luckperms_rs::groups::GetGroup;
+use luckperms_rs::models::{Node, NodeType, TemporaryMergeStrategy};
+use luckperms_rs::testing::FakeLuckPerms;
+use reqwest::Method;
+use uuid::Uuid;
+
+/// An endpoint the crate has no method for, parsed loosely.
+struct RawGroupMeta(String);
+
+impl Endpoint for RawGroupMeta {
+    type Response = serde_json::Value;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path(&self) -> Vec<String> {
+        vec!["group".to_string(), self.0.clone(), "meta".to_string()]
+    }
+}
+
+fn prefix(key: &str) -> Node {
+    Node {
+        key: key.to_string(),
+        type_: NodeType::Prefix,
+        value: true,
+        context: Default::default(),
+        expiry: None,
+    }
+}
+
+#[tokio::test]
+async fn execute_custom_endpoint() {
+    let fake = FakeLuckPerms::new();
+    fake.insert_group("admin", vec![prefix("prefix.100.[Admin]")]);
+    let client = fake.client();
+
+    let meta = client.execute(RawGroupMeta("admin".to_string())).await.unwrap();
+    assert_eq!(meta["prefix"], "[Admin]");
+
+    let group = client.execute(GetGroup { name: "admin".to_string() }).await.unwrap();
+    assert_eq!(group.name, "admin");
+}
+
+#[tokio::test]
+async fn user_and_group_metadata_agree() {
+    let fake = FakeLuckPerms::new();
+    let uuid = Uuid::new_v4();
+    fake.insert_user(uuid, "notch");
+    let client = fake.client();
+
+    let nodes = client.add_user_node(uuid, prefix("prefix.10.[User]"), TemporaryMergeStrategy::None).await.unwrap();
+    assert!(nodes.iter().any(|node| node.key == "prefix.10.[User]"));
+
+    let user_meta = client.get_user_metadata(uuid).await.unwrap();
+    assert_eq!(user_meta.prefix.as_deref(), Some("[User]"));
+    let group_meta = client.get_group_metadata("default".to_string()).await.unwrap();
+    assert_eq!(group_meta.prefix, None);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::Stub;
+use luckperms_rs::errors::RequestError;
+#[cfg(feature = "testing")]
+use luckperms_rs::models::{Node, NodeType, TemporaryMergeStrategy, UserIdentifier};
+#[cfg(feature = "testing")]
+use luckperms_rs::testing::FakeLuckPerms;
+use reqwest::StatusCode;
+
+#[cfg(feature = "testing")]
+#[tokio::test]
+async fn conflicts_keep_the_server_message() {
+    let client = FakeLuckPerms::new().client();
+
+    let error = client.create_group("default".to_string()).await.unwrap_err();
+    assert!(matches!(error, RequestError::Conflict { message: Some(ref message) } if message == "group already exists"));
+
+    let user = UserIdentifier {
+        unique_id: uuid::Uuid::new_v4(),
+        username: "notch".to_string(),
+    };
+    client.create_user(user.clone()).await.unwrap();
+    let error = client.create_user(user).await.unwrap_err();
+    assert!(matches!(error, RequestError::Conflict { message: Some(ref message) } if message == "user already exists"));
+}
+
+#[cfg(feature = "testing")]
+#[tokio::test]
+async fn missing_groups_are_not_found() {
+    let client = FakeLuckPerms::new().client();
+    let missing = || "missing".to_string();
+    let node = Node {
+        key: "fly".to_string(),
+        type_: NodeType::Permission,
+        value: true,
+        context: Default::default(),
+        expiry: None,
+    };
+
+    assert!(client.get_group(missing()).await.unwrap().is_none());
+    assert!(matches!(client.get_group_nodes(missing()).await, Err(RequestError::NotFound)));
+    assert!(matches!(client.add_group_node(missing(), node, TemporaryMergeStrategy::None).await, Err(RequestError::NotFound)));
+    assert!(matches!(client.get_group_metadata(missing()).await, Err(RequestError::NotFound)));
+    assert!(matches!(client.delete_group(missing()).await, Err(RequestError::NotFound)));
+}
+
+async fn get_groups(status: StatusCode, body: &'static str) -> RequestError {
+    common::client(Stub::always(status, body)).get_groups().await.unwrap_err()
+}
+
+#[tokio::test]
+async fn error_bodies_end_up_in_the_message() {
+    let error = get_groups(StatusCode::BAD_REQUEST, r#"{"message":"invalid name"}"#).await;
+    assert!(matches!(error, RequestError::BadRequest { message: Some(ref message) } if message == "invalid name"));
+
+    let error = get_groups(StatusCode::CONFLICT, r#"{"error":"Conflict","status":409}"#).await;
+    assert!(matches!(error, RequestError::Conflict { message: Some(ref message) } if message == "Conflict"));
+
+    let error = get_groups(StatusCode::BAD_GATEWAY, "upstream unavailable").await;
+    assert!(matches!(
+        error,
+        RequestError::Server { status: StatusCode::BAD_GATEWAY, message: Some(ref message) } if message == "upstream unavailable",
+    ));
+
+    assert!(matches!(get_groups(StatusCode::UNAUTHORIZED, "").await, RequestError::Unauthorized));
+    assert!(matches!(get_groups(StatusCode::FORBIDDEN, "").await, RequestError::Forbidden));
+    assert!(matches!(get_groups(StatusCode::NOT_FOUND, "").await, RequestError::NotFound));
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use std::time::Duration;
+use common::{client, Stub};
+use futures_util::stream::StreamExt;
+use luckperms_rs::errors::RequestError;
+use luckperms_rs::events::{Event, EventKind, ReconnectOptions};
+use luckperms_rs::LuckClient;
+use tokio::time::Instant;
+
+/// Serves `first` as the body of the first event stream, then fails every connection.
+fn unreachable(first: Option<&'static str>) -> Stub {
+    Stub::streaming(move |_, sent| match (sent, first) {
+        (0, Some(body)) => Ok(common::event_stream(&[body], false)),
+        _ => Err(RequestError::Transport("connection refused".into())),
+    })
+}
+
+/// The times, since the start, at which the first `count` errors are yielded.
+async fn error_times(client: &LuckClient, options: ReconnectOptions, count: usize) -> Vec<Duration> {
+    let started = Instant::now();
+    let mut events = client.reconnecting_events(&[EventKind::PreSync], options);
+    let mut times = Vec::new();
+    while times.len() < count {
+        if let Some(Err(_)) = events.next().await {
+            times.push(started.elapsed());
+        }
+    }
+    times
+}
+
+fn options(max_attempts: u32) -> ReconnectOptions {
+    ReconnectOptions {
+        initial_backoff: Duration::from_secs(1),
+        max_backoff: Duration::from_secs(8),
+        max_attempts: Some(max_attempts),
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn keeps_backing_off_after_yielding_errors() {
+    let transport = unreachable(None);
+    let times = error_times(&client(transport.clone()), options(1), 5).await;
+
+    let secs: Vec<u64> = times.iter().map(Duration::as_secs).collect();
+    assert_eq!(secs, [0, 1, 3, 7, 15]);
+    assert_eq!(transport.sent(), 5);
+}
+
+#[tokio::test(start_paused = true)]
+async fn yields_errors_every_max_attempts() {
+    let times = error_times(&client(unreachable(None)), options(2), 2).await;
+
+    let secs: Vec<u64> = times.iter().map(Duration::as_secs).collect();
+    assert_eq!(secs, [1, 7]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn server_retry_is_a_lower_bound() {
+    let times = error_times(&client(unreachable(Some("retry: 3000\n\n"))), options(1), 4).await;
+
+    let secs: Vec<u64> = times.iter().map(Duration::as_secs).collect();
+    assert_eq!(secs, [3, 6, 10, 18]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn reconnects_resume_from_the_last_event_id() {
+    // Serve one event with id 7 and close, then keep later connections open.
+    let transport = Stub::streaming(|_, sent| Ok(match sent {
+        0 => common::event_stream(&["id: 7\ndata: {}\n\n"], false),
+        _ => common::event_stream(&[], true),
+    }));
+    let client = client(transport.clone());
+    let mut events = client.reconnecting_events(&[EventKind::PreSync], options(1));
+
+    assert!(matches!(events.next().await, Some(Ok(Event::PreSync))));
+    let Some(Ok(Event::Gap(gap))) = events.next().await else {
+        panic!("expected a gap after the connection closed");
+    };
+    assert_eq!(gap.kind, EventKind::PreSync);
+    assert_eq!(gap.resumed_from.as_deref(), Some("7"));
+    assert!(gap.disconnected_at <= gap.reconnected_at);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].headers.get("last-event-id").is_none());
+    assert_eq!(requests[1].headers.get("last-event-id").unwrap(), "7");
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use std::time::Duration;
+use common::{client, Stub};
+use luckperms_rs::errors::RequestError;
+use reqwest::StatusCode;
+use tokio::time::Instant;
+
+#[tokio::test(start_paused = true)]
+async fn hanging_check_does_not_outlive_the_timeout() {
+    let started = Instant::now();
+    let hanging = Stub::with_future(|_, _| Box::pin(futures_util::future::pending()));
+    let error = client(hanging).wait_until_healthy(Duration::from_secs(5)).await.unwrap_err();
+
+    assert_eq!(started.elapsed(), Duration::from_secs(5));
+    assert!(matches!(error, RequestError::HealthTimeout { health: None, error: Some(_) }));
+}
+
+#[tokio::test(start_paused = true)]
+async fn timeout_reports_the_last_health() {
+    let unhealthy = Stub::always(
+        StatusCode::SERVICE_UNAVAILABLE,
+        r#"{"healthy":false,"details":{"storageConnected":false,"reason":"storage is down"}}"#,
+    );
+    let error = client(unhealthy).wait_until_healthy(Duration::from_secs(2)).await.unwrap_err();
+
+    let RequestError::HealthTimeout { health: Some(health), error: None } = &error else {
+        panic!("unexpected error {:?}", error);
+    };
+    assert_eq!(health.details.reason.as_deref(), Some("storage is down"));
+    assert!(error.to_string().ends_with("last reported unhealthy: storage is down"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn client_errors_fail_straight_away() {
+    let client = client(Stub::always(StatusCode::UNAUTHORIZED, "Unauthorized"));
+    assert!(matches!(client.health().await, Err(RequestError::Unauthorized)));
+
+    let started = Instant::now();
+    let error = client.wait_until_healthy(Duration::from_secs(30)).await.unwrap_err();
+    assert!(matches!(error, RequestError::Unauthorized));
+    assert_eq!(started.elapsed(), Duration::ZERO);
+}
+
+#[tokio::test]
+async fn unbounded_timeout() {
+    assert!(client(Stub::always(StatusCode::OK, r#"{"healthy":true}"#)).wait_until_healthy(Duration::MAX).await.unwrap().healthy);
+}
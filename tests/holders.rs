@@ -0,0 +1,45 @@
+#![cfg(feature = "testing")]
+
+use luckperms_rs::holders::HolderRef;
+use luckperms_rs::models::{Node, NodeType, TemporaryMergeStrategy, Tristate};
+use luckperms_rs::testing::FakeLuckPerms;
+use uuid::Uuid;
+
+fn permission(key: &str) -> Node {
+    Node {
+        key: key.to_string(),
+        type_: NodeType::Permission,
+        value: true,
+        context: Default::default(),
+        expiry: None,
+    }
+}
+
+#[tokio::test]
+async fn holders_share_code_paths() {
+    let fake = FakeLuckPerms::new();
+    let uuid = Uuid::new_v4();
+    fake.insert_user(uuid, "notch");
+    fake.insert_group("builder", Vec::new());
+    let client = fake.client();
+
+    for holder in [HolderRef::from(uuid), HolderRef::Group("builder".to_string())] {
+        let nodes = holder.add_nodes(&client, vec![permission("worldedit.wand"), permission("essentials.fly")], TemporaryMergeStrategy::None).await.unwrap();
+        assert!(nodes.contains(&permission("worldedit.wand")));
+
+        let check = holder.check_permission(&client, "worldedit.wand".to_string()).await.unwrap();
+        assert_eq!(check.result, Tristate::True, "{holder:?}");
+
+        holder.delete_nodes(&client, vec![permission("worldedit.wand")]).await.unwrap();
+        let nodes = holder.get_nodes(&client).await.unwrap();
+        assert!(!nodes.contains(&permission("worldedit.wand")));
+        assert!(nodes.contains(&permission("essentials.fly")));
+
+        holder.set_nodes(&client, Vec::new()).await.unwrap();
+        assert!(!holder.get_nodes(&client).await.unwrap().contains(&permission("essentials.fly")));
+        assert!(holder.metadata(&client).await.unwrap().meta.is_empty());
+    }
+
+    let user_nodes = client.get_user_nodes(uuid).await.unwrap();
+    assert_eq!(user_nodes, HolderRef::User(uuid).get_nodes(&client).await.unwrap());
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use common::Stub;
+use futures_util::future::join_all;
+use luckperms_rs::limiter::Limiter;
+use luckperms_rs::LuckClient;
+use reqwest::StatusCode;
+use tokio::time::Instant;
+
+/// Counts how many requests are in flight, and how many were answered.
+#[derive(Debug, Clone, Default)]
+struct Counters {
+    in_flight: Arc<AtomicUsize>,
+    max_in_flight: Arc<AtomicUsize>,
+    answered: Arc<AtomicUsize>,
+}
+
+fn client(limiter: Limiter) -> (LuckClient, Counters) {
+    let counters = Counters::default();
+    let slow = Stub::with_future({
+        let counters = counters.clone();
+        move |_, _| {
+            let counters = counters.clone();
+            Box::pin(async move {
+                let in_flight = counters.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                counters.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                counters.in_flight.fetch_sub(1, Ordering::SeqCst);
+                counters.answered.fetch_add(1, Ordering::SeqCst);
+                Ok(common::response(StatusCode::OK, "[]"))
+            })
+        }
+    });
+    (common::builder(slow).limiter(limiter).build().unwrap(), counters)
+}
+
+#[tokio::test(start_paused = true)]
+async fn limits_in_flight_requests() {
+    let (client, counters) = client(Limiter::new().max_in_flight(2));
+
+    let started = Instant::now();
+    let results = join_all((0..6).map(|_| client.get_groups())).await;
+
+    assert!(results.iter().all(Result::is_ok));
+    assert_eq!(counters.max_in_flight.load(Ordering::SeqCst), 2);
+    assert_eq!(started.elapsed(), Duration::from_secs(3));
+}
+
+#[tokio::test(start_paused = true)]
+async fn limits_request_rate() {
+    let (client, _) = client(Limiter::new().rate_limit(2, Duration::from_secs(1)));
+
+    let started = Instant::now();
+    let results = join_all((0..6).map(|_| client.get_groups())).await;
+
+    assert!(results.iter().all(Result::is_ok));
+    // Two requests in the initial burst, then one every half second, each taking a second.
+    assert_eq!(started.elapsed(), Duration::from_secs(3));
+}
+
+#[tokio::test(start_paused = true)]
+async fn cancelled_waiters_release_their_place() {
+    let (client, counters) = client(Limiter::new().max_in_flight(1));
+
+    let cancelled = tokio::time::timeout(Duration::from_millis(500), join_all([client.get_groups(), client.get_groups()])).await;
+    assert!(cancelled.is_err());
+
+    let started = Instant::now();
+    client.get_groups().await.unwrap();
+    assert_eq!(started.elapsed(), Duration::from_secs(1));
+    assert_eq!(counters.answered.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn zero_limits_are_treated_as_one() {
+    let (client, counters) = client(Limiter::new().max_in_flight(0).rate_limit(0, Duration::from_secs(1)));
+
+    let started = Instant::now();
+    let results = join_all((0..3).map(|_| client.get_groups())).await;
+
+    assert!(results.iter().all(Result::is_ok));
+    assert_eq!(counters.max_in_flight.load(Ordering::SeqCst), 1);
+    assert_eq!(started.elapsed(), Duration::from_secs(3));
+}
+
+#[tokio::test(start_paused = true)]
+async fn huge_limits_are_capped() {
+    let (client, counters) = client(Limiter::new().max_in_flight(usize::MAX));
+
+    let started = Instant::now();
+    let results = join_all((0..6).map(|_| client.get_groups())).await;
+
+    assert!(results.iter().all(Result::is_ok));
+    assert_eq!(counters.max_in_flight.load(Ordering::SeqCst), 6);
+    assert_eq!(started.elapsed(), Duration::from_secs(1));
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use common::Stub;
+use reqwest::StatusCode;
+use uuid::Uuid;
+
+/// The path and body of every request sent to `transport`.
+fn sent(transport: &Stub) -> Vec<(String, String)> {
+    transport.requests()
+        .into_iter()
+        .map(|request| (request.url.path().to_string(), String::from_utf8(request.body.unwrap_or_default()).unwrap()))
+        .collect()
+}
+
+#[tokio::test]
+async fn sends_json_encoded_custom_messages() {
+    let transport = Stub::always(StatusCode::OK, "");
+    let client = common::client(transport.clone());
+
+    client.send_custom_message("chat".to_string(), &"hello").await.unwrap();
+    client.send_custom_message("scores".to_string(), &[1, 2]).await.unwrap();
+
+    assert_eq!(sent(&transport), [
+        ("/messaging/custom".to_string(), r#"{"channelId":"chat","payload":"\"hello\""}"#.to_string()),
+        ("/messaging/custom".to_string(), r#"{"channelId":"scores","payload":"[1,2]"}"#.to_string()),
+    ]);
+}
+
+#[tokio::test]
+async fn pushes_updates() {
+    let transport = Stub::always(StatusCode::OK, "");
+    let client = common::client(transport.clone());
+    let uuid = Uuid::new_v4();
+
+    client.push_network_update().await.unwrap();
+    client.push_user_update(uuid).await.unwrap();
+
+    let paths: Vec<String> = sent(&transport).into_iter().map(|(path, _)| path).collect();
+    assert_eq!(paths, ["/messaging/update".to_string(), format!("/messaging/update/{}", uuid)]);
+}
+
+#[cfg(feature = "testing")]
+#[tokio::test]
+async fn fake_server_records_messages() {
+    let fake = luckperms_rs::testing::FakeLuckPerms::new();
+    let client = fake.client();
+
+    client.push_network_update().await.unwrap();
+    client.push_user_update(Uuid::new_v4()).await.unwrap();
+    client.send_custom_message("chat".to_string(), &"hello").await.unwrap();
+
+    assert_eq!(fake.network_updates(), 2);
+    let messages = fake.custom_messages();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].channel_id, "chat");
+    assert_eq!(messages[0].payload, r#""hello""#);
+    assert_eq!(messages[0].payload_json::<String>().unwrap(), "hello");
+}
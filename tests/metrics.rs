@@ -0,0 +1,37 @@
+use std::sync::{Arc, Mutex};
+use luckperms_rs::metrics::{MetricsRecorder, RequestMetrics};
+use luckperms_rs::LuckClient;
+use reqwest::{Method, StatusCode};
+
+#[derive(Debug, Clone, Default)]
+struct Recorded(Arc<Mutex<Vec<RequestMetrics>>>);
+
+impl MetricsRecorder for Recorded {
+    fn record(&self, metrics: &RequestMetrics) {
+        self.0.lock().unwrap().push(metrics.clone());
+    }
+}
+
+#[tokio::test]
+async fn records_every_call() {
+    let recorded = Recorded::default();
+    let path = format!("{}/tests/cassettes/group.json", env!("CARGO_MANIFEST_DIR"));
+    let client = LuckClient::builder("http://localhost:8080".to_string(), "key".to_string())
+        .replay_cassette(path)
+        .metrics_recorder(recorded.clone())
+        .build()
+        .unwrap();
+
+    client.get_group("admin".to_string()).await.unwrap();
+    client.get_group("missing".to_string()).await.unwrap();
+    assert!(client.get_group("unrecorded".to_string()).await.is_err());
+
+    let metrics = recorded.0.lock().unwrap();
+    assert_eq!(metrics.len(), 3);
+    assert!(metrics.iter().all(|m| m.endpoint == "/group/{name}" && m.method == Method::GET && m.attempts == 1));
+    assert_eq!(metrics[0].status, Some(StatusCode::OK));
+    assert!(metrics[0].response_bytes > 0);
+    assert_eq!(metrics[1].status_class(), "4xx");
+    assert_eq!(metrics[2].status, None);
+    assert_eq!(metrics[2].status_class(), "error");
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use common::Stub;
+use luckperms_rs::errors::RequestError;
+use luckperms_rs::middleware::Middleware;
+use luckperms_rs::transport::{HttpRequest, HttpResponse};
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::StatusCode;
+
+const CORRELATION_ID: HeaderName = HeaderName::from_static("x-correlation-id");
+
+#[derive(Debug)]
+struct CorrelationId(&'static str);
+
+impl Middleware for CorrelationId {
+    fn before_request(&self, request: &mut HttpRequest) -> Result<(), RequestError> {
+        request.headers.insert(CORRELATION_ID, HeaderValue::from_static(self.0));
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Log(Arc<Mutex<Vec<String>>>);
+
+impl Middleware for Log {
+    fn before_request(&self, request: &mut HttpRequest) -> Result<(), RequestError> {
+        self.0.lock().unwrap().push(format!("before {}", request.url.path()));
+        Ok(())
+    }
+
+    fn after_response(&self, request: &HttpRequest, response: &mut HttpResponse, _elapsed: Duration) -> Result<(), RequestError> {
+        let id = request.headers.get(CORRELATION_ID).and_then(|id| id.to_str().ok()).unwrap_or("none");
+        self.0.lock().unwrap().push(format!("after {} {} {}", request.url.path(), response.status.as_u16(), id));
+        Ok(())
+    }
+
+    fn on_error(&self, request: &HttpRequest, error: &RequestError, _elapsed: Duration) {
+        self.0.lock().unwrap().push(format!("error {} {}", request.url.path(), error));
+    }
+}
+
+#[tokio::test]
+async fn middleware_runs_in_order() {
+    let transport = Stub::always(StatusCode::OK, "[]");
+    let log = Log::default();
+    let entries = log.0.clone();
+    let client = common::builder(transport.clone())
+        .middleware(log)
+        .middleware(CorrelationId("abc"))
+        .build()
+        .unwrap();
+
+    client.get_groups().await.unwrap();
+
+    let headers = &transport.requests()[0].headers;
+    assert_eq!(headers.get(CORRELATION_ID).unwrap(), "abc");
+    assert_eq!(headers.get("x-api-key").unwrap(), "key");
+    assert_eq!(*entries.lock().unwrap(), ["before /group", "after /group 200 abc"]);
+}
+
+#[derive(Debug)]
+struct Reject;
+
+impl Middleware for Reject {
+    fn after_response(&self, _request: &HttpRequest, _response: &mut HttpResponse, _elapsed: Duration) -> Result<(), RequestError> {
+        Err(RequestError::Forbidden)
+    }
+}
+
+#[tokio::test]
+async fn middleware_errors_fail_the_call() {
+    let client = common::builder(Stub::always(StatusCode::OK, "[]"))
+        .middleware(Reject)
+        .build()
+        .unwrap();
+
+    assert!(matches!(client.get_groups().await, Err(RequestError::Forbidden)));
+}
+
+#[tokio::test]
+async fn middleware_sees_failed_requests() {
+    let log = Log::default();
+    let entries = log.0.clone();
+    let unreachable = Stub::new(|_, _| Err(RequestError::Transport("connection reset".into())));
+    let client = common::builder(unreachable)
+        .middleware(log)
+        .build()
+        .unwrap();
+
+    assert!(matches!(client.get_groups().await, Err(RequestError::Transport(_))));
+    assert_eq!(*entries.lock().unwrap(), ["before /group", "error /group Transport error: connection reset"]);
+}
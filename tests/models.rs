@@ -0,0 +1,75 @@
+use luckperms_rs::models::{Context, ContextSet, Node, NodeType, TrackMoveResponse};
+use luckperms_rs::requests::QueryOptions;
+
+#[test]
+fn track_move_groups_are_optional() {
+    let added: TrackMoveResponse = serde_json::from_str(r#"{"success":true,"status":"added_to_first","groupTo":"helper"}"#).unwrap();
+    assert_eq!(added.group_from, None);
+    assert_eq!(added.group_to.as_deref(), Some("helper"));
+
+    let removed: TrackMoveResponse = serde_json::from_str(r#"{"success":true,"status":"removed_from_first","groupFrom":"helper"}"#).unwrap();
+    assert_eq!(removed.group_from.as_deref(), Some("helper"));
+    assert_eq!(removed.group_to, None);
+    assert_eq!(serde_json::to_string(&removed).unwrap(), r#"{"success":true,"status":"removed_from_first","groupFrom":"helper"}"#);
+}
+
+#[test]
+fn query_options_round_trip_without_flags() {
+    let options = QueryOptions::default();
+    let json = serde_json::to_string(&options).unwrap();
+    assert_eq!(json, "{}");
+
+    let read: QueryOptions = serde_json::from_str(&json).unwrap();
+    assert!(read.flags.is_empty());
+}
+
+#[test]
+fn context_set_wire_format() {
+    let contexts: ContextSet = serde_json::from_str(r#"[{"key":"world","value":"nether"}]"#).unwrap();
+    assert!(contexts.contains("world", "nether"));
+    assert_eq!(contexts.len(), 1);
+    assert_eq!(serde_json::to_string(&contexts).unwrap(), r#"[{"key":"world","value":"nether"}]"#);
+}
+
+#[test]
+fn context_set_is_canonical() {
+    let unordered: ContextSet = serde_json::from_str(r#"[
+        {"key":"world","value":"nether"},
+        {"key":"server","value":"survival"},
+        {"key":"world","value":"end"},
+        {"key":"server","value":"survival"}
+    ]"#).unwrap();
+    let ordered: ContextSet = [("server", "survival"), ("world", "end"), ("world", "nether")].into_iter().collect();
+
+    assert_eq!(unordered, ordered);
+    assert_eq!(
+        unordered.iter().cloned().collect::<Vec<_>>(),
+        [Context::new("server", "survival"), Context::new("world", "end"), Context::new("world", "nether")],
+    );
+    assert_eq!(serde_json::to_string(&unordered).unwrap(), serde_json::to_string(&ordered).unwrap());
+}
+
+#[test]
+fn context_is_still_exported_from_requests() {
+    let context: luckperms_rs::requests::Context = Context::new("world", "nether");
+    assert_eq!(context.key, "world");
+}
+
+#[test]
+fn unknown_node_type_round_trips() {
+    let json = r#"{"key":"future.node","type":"some_future_type","value":true,"context":[{"key":"world","value":"nether"}],"expiry":null}"#;
+    let node: Node = serde_json::from_str(json).unwrap();
+
+    assert_eq!(node.type_, NodeType::Unknown("some_future_type".to_string()));
+    assert_eq!(serde_json::to_string(&node).unwrap(), json);
+}
+
+#[test]
+fn permission_node_type() {
+    let json = r#"{"key":"luckperms.user.info","type":"permission","value":false,"context":[],"expiry":1893456000}"#;
+    let node: Node = serde_json::from_str(json).unwrap();
+
+    assert_eq!(node.type_, NodeType::Permission);
+    assert_eq!(node.type_.to_string(), "permission");
+    assert_eq!(serde_json::to_string(&node).unwrap(), json);
+}
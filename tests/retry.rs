@@ -0,0 +1,96 @@
+mod common;
+
+use std::time::Duration;
+use common::Stub;
+use luckperms_rs::endpoint::Endpoint;
+use luckperms_rs::errors::RequestError;
+use luckperms_rs::holders::{AddNodes, HolderRef};
+use luckperms_rs::models::TemporaryMergeStrategy;
+use luckperms_rs::retry::{ExponentialBackoff, RetryPolicy};
+use luckperms_rs::tracks::UpdateTrack;
+use luckperms_rs::users::UpdateUsername;
+use reqwest::StatusCode;
+use tokio::time::Instant;
+
+fn policy() -> ExponentialBackoff {
+    ExponentialBackoff {
+        max_retries: 3,
+        initial_delay: Duration::from_millis(200),
+        max_delay: Duration::from_secs(1),
+        retry_non_idempotent: false,
+    }
+}
+
+/// Make a GET and a POST request against a transport failing with `status`, returning how often each was sent.
+async fn attempts(status: StatusCode, policy: ExponentialBackoff) -> (usize, usize) {
+    let transport = Stub::always(status, "");
+    let client = common::builder(transport.clone())
+        .retry_policy(policy)
+        .build()
+        .unwrap();
+
+    assert!(client.get_groups().await.is_err());
+    let idempotent = transport.sent();
+    transport.reset();
+    assert!(client.create_group("vip".to_string()).await.is_err());
+    (idempotent, transport.sent())
+}
+
+#[tokio::test(start_paused = true)]
+async fn retries_transient_statuses() {
+    for status in [StatusCode::BAD_GATEWAY, StatusCode::SERVICE_UNAVAILABLE, StatusCode::GATEWAY_TIMEOUT, StatusCode::TOO_MANY_REQUESTS] {
+        assert_eq!(attempts(status, policy()).await.0, 4, "{}", status);
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn does_not_retry_permanent_errors() {
+    for status in [StatusCode::BAD_REQUEST, StatusCode::UNAUTHORIZED, StatusCode::NOT_FOUND, StatusCode::CONFLICT, StatusCode::INTERNAL_SERVER_ERROR] {
+        assert_eq!(attempts(status, policy()).await.0, 1, "{}", status);
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn honours_max_retries() {
+    let policy = ExponentialBackoff {
+        max_retries: 5,
+        ..policy()
+    };
+    assert_eq!(attempts(StatusCode::SERVICE_UNAVAILABLE, policy).await.0, 6);
+}
+
+#[tokio::test(start_paused = true)]
+async fn retries_non_idempotent_requests_only_when_enabled() {
+    assert_eq!(attempts(StatusCode::SERVICE_UNAVAILABLE, policy()).await.1, 1);
+
+    let policy = ExponentialBackoff {
+        retry_non_idempotent: true,
+        ..policy()
+    };
+    assert_eq!(attempts(StatusCode::SERVICE_UNAVAILABLE, policy).await.1, 4);
+}
+
+#[tokio::test(start_paused = true)]
+async fn delays_stay_below_max_delay() {
+    let policy = ExponentialBackoff {
+        max_retries: 10,
+        ..policy()
+    };
+    let error = RequestError::from_status(StatusCode::SERVICE_UNAVAILABLE, None);
+    for attempt in 1..=10 {
+        let delay = policy.retry_after(attempt, &error, true).unwrap();
+        assert!(delay <= policy.max_delay, "attempt {} waited {:?}", attempt, delay);
+    }
+
+    let started = Instant::now();
+    attempts(StatusCode::SERVICE_UNAVAILABLE, policy).await;
+    assert!(started.elapsed() <= Duration::from_secs(10));
+}
+
+#[test]
+fn replacing_patches_are_idempotent() {
+    let uuid = uuid::Uuid::nil();
+    assert!(UpdateTrack { name: "staff".to_string(), groups: Vec::new() }.idempotent());
+    assert!(UpdateUsername { uuid, username: "notch".to_string() }.idempotent());
+    assert!(!AddNodes { holder: HolderRef::User(uuid), nodes: Vec::new(), merge_strategy: TemporaryMergeStrategy::None }.idempotent());
+}
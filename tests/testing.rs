@@ -0,0 +1,197 @@
+#![cfg(feature = "testing")]
+
+use std::time::{SystemTime, UNIX_EPOCH};
+use luckperms_rs::models::{ContextSet, Node, NodeType, TemporaryMergeStrategy, TrackMoveResponse, Tristate};
+use luckperms_rs::requests::{PermissionCheckRequest, SearchRequest};
+use luckperms_rs::testing::FakeLuckPerms;
+use luckperms_rs::LuckClient;
+use uuid::Uuid;
+
+fn node(key: &str, type_: NodeType, value: bool) -> Node {
+    Node {
+        key: key.to_string(),
+        type_,
+        value,
+        context: ContextSet::new(),
+        expiry: None,
+    }
+}
+
+fn permission(key: &str) -> Node {
+    node(key, NodeType::Permission, true)
+}
+
+fn inheritance(group: &str) -> Node {
+    node(&format!("group.{}", group), NodeType::Inheritance, true)
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+fn user(fake: &FakeLuckPerms) -> Uuid {
+    let uuid = Uuid::new_v4();
+    fake.insert_user(uuid, "notch");
+    uuid
+}
+
+fn assert_move(response: TrackMoveResponse, success: bool, status: &str, from: Option<&str>, to: Option<&str>) {
+    assert_eq!(
+        (response.success, response.status.as_str(), response.group_from.as_deref(), response.group_to.as_deref()),
+        (success, status, from, to),
+    );
+}
+
+#[tokio::test]
+async fn moves_along_tracks_with_luckperms_statuses() {
+    let fake = FakeLuckPerms::new();
+    fake.insert_track("staff", vec!["helper".to_string(), "mod".to_string(), "admin".to_string()]);
+    let client = fake.client();
+    let uuid = user(&fake);
+    let promote = || client.promote_user(uuid, "staff".to_string());
+    let demote = || client.demote_user(uuid, "staff".to_string());
+
+    assert_move(demote().await.unwrap(), false, "not_on_track", None, None);
+    assert_move(promote().await.unwrap(), true, "added_to_first", None, Some("helper"));
+    assert_move(promote().await.unwrap(), true, "success", Some("helper"), Some("mod"));
+    assert_move(promote().await.unwrap(), true, "success", Some("mod"), Some("admin"));
+    assert_move(promote().await.unwrap(), false, "end_of_track", Some("admin"), None);
+    assert_move(demote().await.unwrap(), true, "success", Some("admin"), Some("mod"));
+    assert_move(demote().await.unwrap(), true, "success", Some("mod"), Some("helper"));
+    assert_move(demote().await.unwrap(), true, "removed_from_first", Some("helper"), None);
+    assert_eq!(client.get_user(uuid).await.unwrap().unwrap().parent_groups, ["default"]);
+}
+
+#[tokio::test]
+async fn rejects_ambiguous_and_malformed_track_moves() {
+    let fake = FakeLuckPerms::new();
+    fake.insert_track("staff", vec!["helper".to_string(), "mod".to_string()]);
+    fake.insert_track("empty", Vec::new());
+    let client = fake.client();
+    let uuid = user(&fake);
+    client.add_user_nodes(uuid, vec![inheritance("helper"), inheritance("mod")], TemporaryMergeStrategy::None).await.unwrap();
+
+    assert_move(client.promote_user(uuid, "staff".to_string()).await.unwrap(), false, "ambiguous_call", None, None);
+    assert_move(client.promote_user(uuid, "empty".to_string()).await.unwrap(), false, "malformed_track", None, None);
+}
+
+/// Add a temporary node expiring in `existing` seconds, then the same node expiring in
+/// `added` seconds, returning the expiries of the resulting nodes.
+async fn merge(strategy: TemporaryMergeStrategy, existing: u64, added: u64) -> Vec<u64> {
+    let fake = FakeLuckPerms::new();
+    let client = fake.client();
+    let uuid = user(&fake);
+    let temporary = |seconds: u64| Node {
+        expiry: Some(now() + seconds),
+        ..permission("fly")
+    };
+
+    client.add_user_node(uuid, temporary(existing), strategy).await.unwrap();
+    let nodes = client.add_user_node(uuid, temporary(added), strategy).await.unwrap();
+    nodes.iter()
+        .filter(|node| node.key == "fly")
+        .map(|node| node.expiry.unwrap() - now())
+        .collect()
+}
+
+fn assert_close(actual: &[u64], expected: &[u64]) {
+    assert_eq!(actual.len(), expected.len(), "{:?} != {:?}", actual, expected);
+    for (actual, expected) in actual.iter().zip(expected) {
+        assert!(actual.abs_diff(*expected) <= 2, "{:?} != {:?}", actual, expected);
+    }
+}
+
+#[tokio::test]
+async fn merges_temporary_nodes_by_strategy() {
+    assert_close(&merge(TemporaryMergeStrategy::AddNewDurationToExisting, 100, 50).await, &[150]);
+    assert_close(&merge(TemporaryMergeStrategy::ReplaceExistingIfDurationLonger, 100, 50).await, &[100]);
+    assert_close(&merge(TemporaryMergeStrategy::ReplaceExistingIfDurationLonger, 100, 200).await, &[200]);
+    assert_close(&merge(TemporaryMergeStrategy::None, 100, 50).await, &[100, 50]);
+}
+
+async fn check(client: &LuckClient, uuid: Uuid, permission: &str) -> Tristate {
+    client.check_user_permission(uuid, permission.to_string()).await.unwrap().result
+}
+
+#[tokio::test]
+async fn resolves_inherited_permissions() {
+    let fake = FakeLuckPerms::new();
+    fake.insert_group("base", vec![permission("build"), node("chat", NodeType::Permission, false)]);
+    fake.insert_group("mid", vec![inheritance("base")]);
+    let client = fake.client();
+    let uuid = user(&fake);
+    client.add_user_node(uuid, inheritance("mid"), TemporaryMergeStrategy::None).await.unwrap();
+
+    let result = client.check_user_permission(uuid, "build".to_string()).await.unwrap();
+    assert_eq!(result.result, Tristate::True);
+    assert_eq!(result.node.unwrap().key, "build");
+    assert_eq!(check(&client, uuid, "chat").await, Tristate::False);
+    assert_eq!(check(&client, uuid, "fly").await, Tristate::Undefined);
+
+    // A user's own nodes take precedence over inherited ones.
+    client.add_user_node(uuid, node("build", NodeType::Permission, false), TemporaryMergeStrategy::None).await.unwrap();
+    assert_eq!(check(&client, uuid, "build").await, Tristate::False);
+
+    // Expired inheritance no longer applies.
+    let expired = Node {
+        expiry: Some(now() - 1),
+        ..inheritance("base")
+    };
+    let other = user(&fake);
+    client.set_user_nodes(other, vec![expired]).await.unwrap();
+    assert_eq!(check(&client, other, "build").await, Tristate::Undefined);
+}
+
+#[tokio::test]
+async fn contextual_permissions_need_matching_contexts() {
+    let fake = FakeLuckPerms::new();
+    let nether: ContextSet = [("world", "nether")].into_iter().collect();
+    fake.insert_group("base", vec![Node {
+        context: nether.clone(),
+        ..permission("fly")
+    }]);
+    let client = fake.client();
+    let uuid = user(&fake);
+    client.add_user_node(uuid, inheritance("base"), TemporaryMergeStrategy::None).await.unwrap();
+
+    assert_eq!(check(&client, uuid, "fly").await, Tristate::Undefined);
+
+    let mut request = PermissionCheckRequest::new("fly");
+    request.query_options.contexts = nether;
+    assert_eq!(client.check_user_permission_query(uuid, request).await.unwrap().result, Tristate::True);
+}
+
+#[tokio::test]
+async fn resolves_metadata_by_priority() {
+    let fake = FakeLuckPerms::new();
+    fake.insert_group("base", vec![
+        node("prefix.10.[Member]", NodeType::Prefix, true),
+        node("prefix.20.[Staff]", NodeType::Prefix, true),
+        node("meta.rank.1", NodeType::Meta, true),
+        node("meta.color.red", NodeType::Meta, true),
+    ]);
+    let client = fake.client();
+    let uuid = user(&fake);
+    client.set_user_nodes(uuid, vec![node("meta.rank.2", NodeType::Meta, true), inheritance("base")]).await.unwrap();
+
+    let metadata = client.get_user_metadata(uuid).await.unwrap();
+    assert_eq!(metadata.prefix.as_deref(), Some("[Staff]"));
+    assert_eq!(metadata.meta.get("rank").map(String::as_str), Some("2"));
+    assert_eq!(metadata.meta.get("color").map(String::as_str), Some("red"));
+    assert_eq!(metadata.primary_group.as_deref(), Some("base"));
+}
+
+#[tokio::test]
+async fn searches_nodes() {
+    let fake = FakeLuckPerms::new();
+    fake.insert_group("builders", vec![permission("build.place"), permission("build.break"), permission("chat")]);
+    let client = fake.client();
+
+    let results = client.search_group(SearchRequest::new_key_starts_with("build.".to_string())).await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].name, "builders");
+    assert_eq!(results[0].results.len(), 2);
+
+    let results = client.search_group(SearchRequest::new_key("missing".to_string())).await.unwrap();
+    assert!(results.is_empty());
+}
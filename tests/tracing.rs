@@ -0,0 +1,136 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use common::Stub;
+use luckperms_rs::models::{Node, NodeType, TemporaryMergeStrategy};
+use reqwest::{Method, StatusCode};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Answers `GET /group` and `POST /group/admin/...`, and 404s anything else.
+fn groups() -> Stub {
+    Stub::new(|request, _| {
+        let (status, body) = match (&request.method, request.url.path()) {
+            (&Method::GET, "/group") => (StatusCode::OK, r#"["secret-group"]"#),
+            (&Method::POST, path) if path.starts_with("/group/admin/") => (StatusCode::OK, "[]"),
+            _ => (StatusCode::NOT_FOUND, r#"{"message":"group not found"}"#),
+        };
+        Ok(common::response(status, body))
+    })
+}
+
+type Fields = HashMap<String, String>;
+
+#[derive(Default)]
+struct Fieldset(Fields);
+
+impl Visit for Fieldset {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+/// Keeps the fields of every span, and every event with the fields of its span.
+#[derive(Debug, Clone, Default)]
+struct Captured {
+    spans: Arc<Mutex<HashMap<u64, Fields>>>,
+    events: Arc<Mutex<Vec<(Level, Fields, Fields)>>>,
+}
+
+impl Captured {
+    fn events(&self) -> Vec<(Level, Fields, Fields)> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Captured {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+        let mut fields = Fieldset::default();
+        attrs.record(&mut fields);
+        self.spans.lock().unwrap().insert(id.into_u64(), fields.0);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        let mut fields = Fieldset::default();
+        values.record(&mut fields);
+        self.spans.lock().unwrap().entry(id.into_u64()).or_default().extend(fields.0);
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = Fieldset::default();
+        event.record(&mut fields);
+        let span = ctx.event_span(event)
+            .and_then(|span| self.spans.lock().unwrap().get(&span.id().into_u64()).cloned())
+            .unwrap_or_default();
+        self.events.lock().unwrap().push((*event.metadata().level(), fields.0, span));
+    }
+}
+
+async fn calls(log_payloads: bool) -> Vec<(Level, Fields, Fields)> {
+    let captured = Captured::default();
+    let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(captured.clone()));
+    let client = common::builder(groups())
+        .log_payloads(log_payloads)
+        .build()
+        .unwrap();
+    let node = Node {
+        key: "secret.permission".to_string(),
+        type_: NodeType::Permission,
+        value: true,
+        context: Default::default(),
+        expiry: None,
+    };
+
+    client.get_groups().await.unwrap();
+    client.add_group_node("admin".to_string(), node, TemporaryMergeStrategy::None).await.unwrap();
+    assert!(client.get_group("missing".to_string()).await.unwrap().is_none());
+    captured.events()
+}
+
+fn mentions(events: &[(Level, Fields, Fields)], text: &str) -> bool {
+    events.iter().any(|(_, fields, _)| fields.values().any(|value| value.contains(text)))
+}
+
+#[tokio::test]
+async fn records_requests_on_the_span() {
+    let events = calls(false).await;
+    let finished: Vec<_> = events.iter()
+        .filter(|(_, fields, _)| matches!(fields.get("message").map(String::as_str), Some("request succeeded" | "request failed")))
+        .collect();
+    assert_eq!(finished.len(), 3);
+
+    let (level, _, span) = finished[0];
+    assert_eq!(*level, Level::DEBUG);
+    assert_eq!((span["method"].as_str(), span["status"].as_str(), span["attempts"].as_str()), ("GET", "200", "1"));
+
+    let (_, _, span) = finished[1];
+    assert_eq!((span["method"].as_str(), span["target"].as_str()), ("POST", "admin"));
+
+    // A 404 is an expected outcome, not a warning.
+    let (level, fields, span) = finished[2];
+    assert_eq!(*level, Level::DEBUG);
+    assert_eq!(fields["message"], "request failed");
+    assert_eq!((span["status"].as_str(), span["target"].as_str()), ("404", "missing"));
+}
+
+#[tokio::test]
+async fn payloads_are_only_logged_when_enabled() {
+    let events = calls(false).await;
+    assert!(!mentions(&events, "secret"));
+    assert!(mentions(&events, "request payload redacted"));
+
+    let events = calls(true).await;
+    assert!(mentions(&events, "secret.permission"));
+    assert!(mentions(&events, "secret-group"));
+}